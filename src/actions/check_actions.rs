@@ -0,0 +1,71 @@
+use crate::{
+    actions::{
+        Actionable, KeyBinding, bind,
+        terminal_actions::{show_results, start_check},
+    },
+    app::App,
+    model::guard::quote_identifier,
+    ui::screens::Screen,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug)]
+pub enum CheckActions {}
+
+impl Actionable for CheckActions {
+    fn key_bindings() -> &'static [KeyBinding] {
+        const BINDINGS: &[KeyBinding] = &[
+            bind("Esc", "Cancel a check that is still running"),
+            bind("j / k", "Select the next / previous problem"),
+            bind("Enter", "Show the row the selected problem is about"),
+            bind("r", "Run the check again"),
+            bind("q / Esc", "Return to the terminal"),
+        ];
+        BINDINGS
+    }
+
+    fn take_action(app: &mut App, key_event: KeyEvent) {
+        // global shortcuts arrive here too; Ctrl-R must not run the check again
+        if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+            return;
+        }
+        let Some(Screen::Check(check_screen)) = app.screens.last_mut() else {
+            return;
+        };
+
+        if check_screen.is_running() {
+            if matches!(key_event.code, KeyCode::Esc | KeyCode::Char('q')) {
+                check_screen.cancel();
+            }
+            return;
+        }
+
+        match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => check_screen.next(),
+            KeyCode::Char('k') | KeyCode::Up => check_screen.previous(),
+            KeyCode::Enter => {
+                if let Some((table, rowid)) = check_screen.selected_row() {
+                    let query = format!(
+                        "SELECT rowid, * FROM {} WHERE rowid = {}",
+                        quote_identifier(&table),
+                        rowid
+                    );
+                    if let Some(screen) = show_results(app, query) {
+                        app.push_screen(screen);
+                    }
+                }
+            }
+            KeyCode::Char('r') => {
+                let kind = check_screen.kind;
+                if let Some(screen) = start_check(app, kind) {
+                    app.pop_screen();
+                    app.push_screen(screen);
+                }
+            }
+            KeyCode::Char('q') | KeyCode::Esc => {
+                app.pop_screen();
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{
+    actions::{Actionable, KeyBinding, bind, terminal_actions::run_statement},
+    app::App,
+    ui::screens::Screen,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug)]
+pub enum ConfirmActions {}
+
+impl Actionable for ConfirmActions {
+    fn key_bindings() -> &'static [KeyBinding] {
+        const BINDINGS: &[KeyBinding] = &[
+            bind("y", "Run the guarded statement"),
+            bind("n / Esc", "Cancel the guarded statement"),
+        ];
+        BINDINGS
+    }
+
+    fn take_action(app: &mut App, key_event: KeyEvent) {
+        if !matches!(app.screens.last(), Some(Screen::Confirm(_))) {
+            return;
+        }
+        // Ctrl shortcuts are handled globally first, so Ctrl-Y must not confirm the statement
+        let control = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        match key_event.code {
+            KeyCode::Char('y') if !control => {
+                if let Some(Screen::Confirm(confirm_screen)) = app.pop_screen() {
+                    run_statement(app, &confirm_screen.query);
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                app.pop_screen();
+                app.terminal.add_log_line("Cancelled".to_string());
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,43 +1,67 @@
 use crate::{
-    actions::Actionable,
+    actions::{Actionable, HelpContext, KeyBinding, bind},
     app::App,
     ui::screens::{Screen, help_screen::HelpScreen, quit_screen::QuitScreen},
 };
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 #[derive(Debug)]
-pub enum GlobalActions {
-    Save,
-    Rollback,
-    Quit,
-    Help,
-}
+pub enum GlobalActions {}
 
 fn save(app: &mut App) {
-    app.session.commit();
-    app.notifications
-        .notify("Save", "Changes to database saved successfully.")
+    match app.session.commit() {
+        Ok(()) => app
+            .notifications
+            .notify("Save", "Changes to database saved successfully."),
+        Err(e) => app.notifications.error(e),
+    }
 }
 
 fn rollback(app: &mut App) {
-    app.session.rollback();
-    app.notifications
-        .notify("Rollback", "Staged changes successfully reverted.")
+    match app.session.rollback() {
+        Ok(()) => app
+            .notifications
+            .notify("Rollback", "Staged changes successfully reverted."),
+        Err(e) => app.notifications.error(e),
+    }
 }
 
 impl Actionable for GlobalActions {
+    fn key_bindings() -> &'static [KeyBinding] {
+        const BINDINGS: &[KeyBinding] = &[
+            bind("Ctrl-S", "Commit the current transaction"),
+            bind("Ctrl-R", "Roll back the current transaction"),
+            bind("Ctrl-Q / Ctrl-C", "Quit the application"),
+            bind("Ctrl-H", "Show this help"),
+            bind("Ctrl-O", "Open another database"),
+        ];
+        BINDINGS
+    }
+
     fn take_action(app: &mut App, key_event: KeyEvent) {
         match (key_event.code, key_event.modifiers) {
             (KeyCode::Char('s'), KeyModifiers::CONTROL) => save(app),
             (KeyCode::Char('r'), KeyModifiers::CONTROL) => rollback(app),
-            (KeyCode::Char('q'), KeyModifiers::CONTROL) => {
-                app.screen = Screen::Exiting(QuitScreen::new())
+            (KeyCode::Char('q'), KeyModifiers::CONTROL)
+            | (KeyCode::Char('c'), KeyModifiers::CONTROL)
+                if !matches!(app.screens.last(), Some(Screen::Exiting(_))) =>
+            {
+                app.push_screen(Screen::Exiting(QuitScreen::new(
+                    app.session.pending_statements(),
+                    app.session.has_unsaved_changes(),
+                )))
             }
-            (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
-                app.screen = Screen::Exiting(QuitScreen::new())
+            (KeyCode::Char('h'), KeyModifiers::CONTROL)
+                if !matches!(app.screens.last(), Some(Screen::Help(_))) =>
+            {
+                let context = HelpContext::for_screen(app.screens.last());
+                app.push_screen(Screen::Help(HelpScreen::new(context)))
             }
-            (KeyCode::Char('h'), KeyModifiers::CONTROL) => {
-                app.screen = Screen::Help(HelpScreen::new())
+            // dialogs have to be answered before switching databases
+            (KeyCode::Char('o'), KeyModifiers::CONTROL)
+                if !app.screens.last().is_some_and(Screen::is_popup) =>
+            {
+                app.show_open_screen()
             }
             _ => {}
         }
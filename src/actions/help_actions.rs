@@ -1,19 +1,56 @@
 use crate::{
-    actions::Actionable, app::App, ui::screens::Screen,
-    ui::screens::terminal_screen::TerminalScreen,
+    actions::{Actionable, KeyBinding, bind},
+    app::App,
+    ui::screens::Screen,
 };
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+const HELP_PAGE: u16 = 10;
 
 #[derive(Debug)]
-pub enum HelpActions {
-    ExitHelp,
-}
+pub enum HelpActions {}
 
 impl Actionable for HelpActions {
+    fn key_bindings() -> &'static [KeyBinding] {
+        const BINDINGS: &[KeyBinding] = &[
+            bind("j / k", "Scroll the help (PageDown / PageUp for a page)"),
+            bind("g / G", "Go to the top / bottom of the help"),
+            bind("/", "Search the key bindings; Esc clears the search"),
+            bind("q / Esc", "Close the help"),
+        ];
+        BINDINGS
+    }
+
     fn take_action(app: &mut App, key_event: KeyEvent) {
+        let Some(Screen::Help(help_screen)) = app.screens.last_mut() else {
+            return;
+        };
+
+        // the search prompt captures all typing until it is confirmed
+        if help_screen.searching {
+            match key_event.code {
+                KeyCode::Char(c) if !key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    help_screen.push_search(c)
+                }
+                KeyCode::Backspace => help_screen.pop_search(),
+                KeyCode::Enter => help_screen.searching = false,
+                KeyCode::Esc => help_screen.clear_search(),
+                _ => {}
+            }
+            return;
+        }
+
         match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => help_screen.scroll_down(1),
+            KeyCode::Char('k') | KeyCode::Up => help_screen.scroll_up(1),
+            KeyCode::PageDown => help_screen.scroll_down(HELP_PAGE),
+            KeyCode::PageUp => help_screen.scroll_up(HELP_PAGE),
+            KeyCode::Char('g') | KeyCode::Home => help_screen.scroll_to_top(),
+            KeyCode::Char('G') | KeyCode::End => help_screen.scroll_to_bottom(),
+            KeyCode::Char('/') => help_screen.open_search(),
+            KeyCode::Esc if help_screen.search.is_some() => help_screen.clear_search(),
             KeyCode::Esc | KeyCode::Char('q') => {
-                app.screen = Screen::Terminal(TerminalScreen::new());
+                app.pop_screen();
             }
             _ => {}
         }
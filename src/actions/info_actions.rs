@@ -0,0 +1,43 @@
+use crate::{
+    actions::{Actionable, KeyBinding, bind},
+    app::App,
+    ui::screens::Screen,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug)]
+pub enum InfoActions {}
+
+impl Actionable for InfoActions {
+    fn key_bindings() -> &'static [KeyBinding] {
+        const BINDINGS: &[KeyBinding] = &[
+            bind("j / k", "Scroll the summary"),
+            bind("r", "Read the PRAGMAs again"),
+            bind("q / Esc", "Return to the previous screen"),
+        ];
+        BINDINGS
+    }
+
+    fn take_action(app: &mut App, key_event: KeyEvent) {
+        // global shortcuts arrive here too; Ctrl-R must not refresh
+        if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+            return;
+        }
+        let Some(Screen::Info(info_screen)) = app.screens.last_mut() else {
+            return;
+        };
+
+        match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => info_screen.scroll_down(),
+            KeyCode::Char('k') | KeyCode::Up => info_screen.scroll_up(),
+            KeyCode::Char('r') => match app.session.database_info() {
+                Ok(info) => info_screen.refresh(info),
+                Err(e) => app.notifications.error(e),
+            },
+            KeyCode::Char('q') | KeyCode::Esc => {
+                app.pop_screen();
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,34 +1,139 @@
+mod check_actions;
+mod confirm_actions;
 mod global_actions;
 mod help_actions;
+mod info_actions;
+mod open_actions;
+mod pragma_actions;
+mod query_plan_actions;
 mod quit_actions;
 mod results_actions;
+mod snapshot_actions;
+mod storage_actions;
 mod terminal_actions;
+use crate::actions::check_actions::CheckActions;
+use crate::actions::confirm_actions::ConfirmActions;
 use crate::actions::global_actions::GlobalActions;
 use crate::actions::help_actions::HelpActions;
+use crate::actions::info_actions::InfoActions;
+use crate::actions::open_actions::OpenActions;
+use crate::actions::pragma_actions::PragmaActions;
+use crate::actions::query_plan_actions::QueryPlanActions;
 use crate::actions::quit_actions::QuitActions;
 use crate::actions::results_actions::ResultActions;
+use crate::actions::snapshot_actions::SnapshotActions;
+use crate::actions::storage_actions::StorageActions;
 use crate::actions::terminal_actions::TerminalActions;
 use crate::app::App;
 use crate::ui::screens::Screen;
 use crossterm::event::KeyEvent;
 
-pub enum Actions {
-    Global(GlobalActions),
-    Terminal(TerminalActions),
-    Result(ResultActions),
-    Help(HelpActions),
-    Quit(QuitActions),
-    NoAction,
+/// dispatches key events to the global handler and then the focused screen
+pub enum Actions {}
+
+/// a key and what it does, as listed on the help screen
+#[derive(Debug, Clone, Copy)]
+pub struct KeyBinding {
+    pub keys: &'static str,
+    pub description: &'static str,
 }
 
-impl Default for Actions {
-    fn default() -> Self {
-        Actions::NoAction
-    }
+const fn bind(keys: &'static str, description: &'static str) -> KeyBinding {
+    KeyBinding { keys, description }
 }
 
+/// implemented by variant-free enums that only name the screen whose keys they handle
 pub trait Actionable {
     fn take_action(app: &mut App, key_event: KeyEvent);
+    /// the keys `take_action` responds to
+    fn key_bindings() -> &'static [KeyBinding];
+}
+
+/// the groups key bindings are listed under on the help screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpContext {
+    Global,
+    Terminal,
+    Results,
+    QueryPlan,
+    Snapshots,
+    Open,
+    Info,
+    Check,
+    Pragmas,
+    Storage,
+    Dialogs,
+}
+
+impl HelpContext {
+    pub const ALL: [HelpContext; 11] = [
+        HelpContext::Global,
+        HelpContext::Terminal,
+        HelpContext::Results,
+        HelpContext::QueryPlan,
+        HelpContext::Snapshots,
+        HelpContext::Open,
+        HelpContext::Info,
+        HelpContext::Check,
+        HelpContext::Pragmas,
+        HelpContext::Storage,
+        HelpContext::Dialogs,
+    ];
+
+    /// the section for whichever screen currently has focus
+    pub fn for_screen(screen: Option<&Screen>) -> Self {
+        match screen {
+            None => HelpContext::Terminal,
+            Some(Screen::Results) => HelpContext::Results,
+            Some(Screen::QueryPlan(_)) => HelpContext::QueryPlan,
+            Some(Screen::Snapshots(_)) => HelpContext::Snapshots,
+            Some(Screen::Open(_)) => HelpContext::Open,
+            Some(Screen::Info(_)) => HelpContext::Info,
+            Some(Screen::Check(_)) => HelpContext::Check,
+            Some(Screen::Pragmas(_)) => HelpContext::Pragmas,
+            Some(Screen::Storage(_)) => HelpContext::Storage,
+            Some(Screen::Help(_)) | Some(Screen::Exiting(_)) | Some(Screen::Confirm(_)) => {
+                HelpContext::Dialogs
+            }
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            HelpContext::Global => "Global",
+            HelpContext::Terminal => "Terminal",
+            HelpContext::Results => "Results",
+            HelpContext::QueryPlan => "Query Plan",
+            HelpContext::Snapshots => "Snapshots",
+            HelpContext::Open => "Open Database",
+            HelpContext::Info => "Database Info",
+            HelpContext::Check => "Checks",
+            HelpContext::Pragmas => "PRAGMA Settings",
+            HelpContext::Storage => "Storage",
+            HelpContext::Dialogs => "Dialogs",
+        }
+    }
+
+    pub fn key_bindings(&self) -> Vec<KeyBinding> {
+        match self {
+            HelpContext::Global => GlobalActions::key_bindings().to_vec(),
+            HelpContext::Terminal => TerminalActions::key_bindings().to_vec(),
+            HelpContext::Results => ResultActions::key_bindings().to_vec(),
+            HelpContext::QueryPlan => QueryPlanActions::key_bindings().to_vec(),
+            HelpContext::Snapshots => SnapshotActions::key_bindings().to_vec(),
+            HelpContext::Open => OpenActions::key_bindings().to_vec(),
+            HelpContext::Info => InfoActions::key_bindings().to_vec(),
+            HelpContext::Check => CheckActions::key_bindings().to_vec(),
+            HelpContext::Pragmas => PragmaActions::key_bindings().to_vec(),
+            HelpContext::Storage => StorageActions::key_bindings().to_vec(),
+            HelpContext::Dialogs => [
+                HelpActions::key_bindings(),
+                QuitActions::key_bindings(),
+                ConfirmActions::key_bindings(),
+            ]
+            .concat(),
+        }
+    }
 }
 
 impl Actions {
@@ -37,11 +142,23 @@ impl Actions {
         <GlobalActions as Actionable>::take_action(app, key_event);
 
         // screen specific actions
-        match app.screen {
-            Screen::Terminal(_) => <TerminalActions as Actionable>::take_action(app, key_event),
-            Screen::Results(_) => <ResultActions as Actionable>::take_action(app, key_event),
-            Screen::Help(_) => <HelpActions as Actionable>::take_action(app, key_event),
-            Screen::Exiting(_) => <QuitActions as Actionable>::take_action(app, key_event),
+        match app.screens.last() {
+            None => <TerminalActions as Actionable>::take_action(app, key_event),
+            Some(Screen::Results) => <ResultActions as Actionable>::take_action(app, key_event),
+            Some(Screen::QueryPlan(_)) => {
+                <QueryPlanActions as Actionable>::take_action(app, key_event)
+            }
+            Some(Screen::Help(_)) => <HelpActions as Actionable>::take_action(app, key_event),
+            Some(Screen::Exiting(_)) => <QuitActions as Actionable>::take_action(app, key_event),
+            Some(Screen::Confirm(_)) => <ConfirmActions as Actionable>::take_action(app, key_event),
+            Some(Screen::Snapshots(_)) => {
+                <SnapshotActions as Actionable>::take_action(app, key_event)
+            }
+            Some(Screen::Open(_)) => <OpenActions as Actionable>::take_action(app, key_event),
+            Some(Screen::Info(_)) => <InfoActions as Actionable>::take_action(app, key_event),
+            Some(Screen::Check(_)) => <CheckActions as Actionable>::take_action(app, key_event),
+            Some(Screen::Pragmas(_)) => <PragmaActions as Actionable>::take_action(app, key_event),
+            Some(Screen::Storage(_)) => <StorageActions as Actionable>::take_action(app, key_event),
         }
     }
 }
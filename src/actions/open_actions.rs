@@ -0,0 +1,109 @@
+use crate::{
+    actions::{Actionable, KeyBinding, bind},
+    app::App,
+    model::file_browser::{EntryKind, expand_home},
+    ui::screens::{Screen, open_screen::OpenFocus},
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug)]
+pub enum OpenActions {}
+
+/// opens `path` from the screen, keeping it in the input so a failure can be corrected
+fn open(app: &mut App, path: String) {
+    let Some(Screen::Open(open_screen)) = app.screens.last_mut() else {
+        return;
+    };
+    open_screen.path = path.clone();
+    let policy = open_screen.policy;
+    app.open_database(expand_home(&path).display().to_string(), policy);
+}
+
+impl Actionable for OpenActions {
+    fn key_bindings() -> &'static [KeyBinding] {
+        const BINDINGS: &[KeyBinding] = &[
+            bind("typing", "Edit the path to open"),
+            bind("Tab", "Complete the path; in the lists, switch list"),
+            bind("Enter", "Open the path or the selected database"),
+            bind(
+                "Down / Up",
+                "Move between the path, recent files and the browser",
+            ),
+            bind("Backspace", "In the browser, go to the parent directory"),
+            bind(
+                "y / n",
+                "Create / don't create a database that does not exist",
+            ),
+            bind(
+                "Esc",
+                "Go back to the open database, or quit when there is none",
+            ),
+        ];
+        BINDINGS
+    }
+
+    fn take_action(app: &mut App, key_event: KeyEvent) {
+        // global shortcuts arrive here too and must not be typed into the path
+        if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+            return;
+        }
+        let Some(Screen::Open(open_screen)) = app.screens.last_mut() else {
+            return;
+        };
+
+        if open_screen.confirm_create {
+            match key_event.code {
+                KeyCode::Char('y') => {
+                    let path = expand_home(&open_screen.path).display().to_string();
+                    let policy = open_screen.policy;
+                    app.open_database_with(path, policy, true);
+                }
+                KeyCode::Char('n') | KeyCode::Esc => open_screen.confirm_create = false,
+                _ => {}
+            }
+            return;
+        }
+
+        if open_screen.focus == OpenFocus::Path {
+            match key_event.code {
+                KeyCode::Char(c) => open_screen.push_char(c),
+                KeyCode::Backspace => open_screen.pop_char(),
+                KeyCode::Tab => open_screen.complete(),
+                KeyCode::Down => open_screen.focus = OpenFocus::Files,
+                KeyCode::Enter if !open_screen.path.is_empty() => {
+                    let path = open_screen.path.clone();
+                    open(app, path);
+                }
+                KeyCode::Esc if open_screen.can_cancel => {
+                    app.pop_screen();
+                }
+                KeyCode::Esc => app.exit(),
+                _ => {}
+            }
+            return;
+        }
+
+        match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => open_screen.next(),
+            KeyCode::Char('k') | KeyCode::Up => open_screen.previous(),
+            KeyCode::Tab | KeyCode::BackTab => open_screen.switch_list(),
+            KeyCode::Backspace | KeyCode::Char('h') if open_screen.focus == OpenFocus::Files => {
+                open_screen.browse_parent()
+            }
+            KeyCode::Esc => open_screen.focus = OpenFocus::Path,
+            KeyCode::Enter if open_screen.focus == OpenFocus::Recent => {
+                if let Some(path) = open_screen.selected_recent().cloned() {
+                    open(app, path);
+                }
+            }
+            KeyCode::Enter => match open_screen.selected_entry().cloned() {
+                Some(entry) if entry.kind == EntryKind::Database => {
+                    open(app, entry.path.display().to_string())
+                }
+                Some(entry) => open_screen.browse(entry.path),
+                None => {}
+            },
+            _ => {}
+        }
+    }
+}
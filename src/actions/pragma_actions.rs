@@ -0,0 +1,82 @@
+use crate::{
+    actions::{Actionable, KeyBinding, bind},
+    app::App,
+    ui::screens::Screen,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug)]
+pub enum PragmaActions {}
+
+fn apply(app: &mut App) {
+    let Some(Screen::Pragmas(pragma_screen)) = app.screens.last_mut() else {
+        return;
+    };
+    let (Some(setting), Some(input)) = (pragma_screen.selected(), pragma_screen.input.take())
+    else {
+        return;
+    };
+
+    match app.session.set_pragma(setting, &input) {
+        Ok(value) => app
+            .terminal
+            .add_log_line(format!("{} = {}", setting.name, value)),
+        // keep what was typed so it can be corrected
+        Err(e) => {
+            pragma_screen.input = Some(input);
+            pragma_screen.error = Some(e.to_string());
+        }
+    }
+    // a change to one pragma can move another, e.g. journal_mode and synchronous
+    pragma_screen.refresh(&app.session);
+}
+
+impl Actionable for PragmaActions {
+    fn key_bindings() -> &'static [KeyBinding] {
+        const BINDINGS: &[KeyBinding] = &[
+            bind("j / k", "Select the next / previous pragma"),
+            bind("Enter", "Edit the selected value, then apply it"),
+            bind("Esc", "Cancel an edit"),
+            bind("r", "Read the values again"),
+            bind("q / Esc", "Return to the terminal"),
+        ];
+        BINDINGS
+    }
+
+    fn take_action(app: &mut App, key_event: KeyEvent) {
+        // global shortcuts arrive here too and must not be typed into the value
+        if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+            return;
+        }
+        let Some(Screen::Pragmas(pragma_screen)) = app.screens.last_mut() else {
+            return;
+        };
+
+        if let Some(input) = &mut pragma_screen.input {
+            match key_event.code {
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Enter => apply(app),
+                KeyCode::Esc => {
+                    pragma_screen.input = None;
+                    pragma_screen.error = None;
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => pragma_screen.next(),
+            KeyCode::Char('k') | KeyCode::Up => pragma_screen.previous(),
+            KeyCode::Enter => pragma_screen.edit(),
+            KeyCode::Char('r') => pragma_screen.refresh(&app.session),
+            KeyCode::Char('q') | KeyCode::Esc => {
+                app.pop_screen();
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{
+    actions::{Actionable, KeyBinding, bind},
+    app::App,
+    ui::screens::Screen,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+
+#[derive(Debug)]
+pub enum QueryPlanActions {}
+
+impl Actionable for QueryPlanActions {
+    fn key_bindings() -> &'static [KeyBinding] {
+        const BINDINGS: &[KeyBinding] = &[
+            bind("j / k", "Scroll the plan"),
+            bind("q / Esc", "Return to the previous screen"),
+        ];
+        BINDINGS
+    }
+
+    fn take_action(app: &mut App, key_event: KeyEvent) {
+        if let Some(Screen::QueryPlan(query_plan_screen)) = app.screens.last_mut() {
+            match key_event.code {
+                KeyCode::Char('j') | KeyCode::Down => query_plan_screen.scroll_down(),
+                KeyCode::Char('k') | KeyCode::Up => query_plan_screen.scroll_up(),
+                KeyCode::Char('q') | KeyCode::Esc => {
+                    app.pop_screen();
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -1,19 +1,109 @@
 use crate::{
-    actions::Actionable, app::App, ui::screens::Screen,
-    ui::screens::terminal_screen::TerminalScreen,
+    actions::{Actionable, KeyBinding, bind},
+    app::App,
+    ui::screens::{
+        Screen,
+        quit_screen::{Leaving, QuitChoice},
+    },
 };
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 #[derive(Debug)]
-pub enum QuitActions {
-    ExitApplication,
+pub enum QuitActions {}
+
+/// quits or opens the next database once the transaction has been committed or rolled back
+fn leave(app: &mut App, leaving: Leaving) {
+    app.pop_screen();
+    match leaving {
+        Leaving::Quit => app.exit(),
+        Leaving::Open {
+            path,
+            policy,
+            create,
+        } => app.switch_database(path, policy, create),
+    }
+}
+
+/// stays, with `.save ` typed into the terminal so the in-memory database can be kept
+fn save_first(app: &mut App) {
+    // the terminal sits beneath every other screen, and only takes input once they are closed
+    app.screens.clear();
+    app.terminal.input = ".save ".to_string();
+    app.terminal.cursor_index = app.terminal.input.chars().count();
+}
+
+fn quit_with(app: &mut App, choice: QuitChoice, leaving: Leaving) {
+    match choice {
+        QuitChoice::CommitAndQuit => match app.session.commit() {
+            Ok(()) => leave(app, leaving),
+            // a failed commit keeps the transaction, so stay and let the user decide again
+            Err(e) => {
+                app.notifications.error(e);
+                app.pop_screen();
+            }
+        },
+        QuitChoice::RollbackAndQuit => match app.session.rollback() {
+            // closing the connection rolls back anyway, so a failure is not fatal when quitting
+            Err(e) if matches!(leaving, Leaving::Open { .. }) => {
+                app.notifications.error(e);
+                app.pop_screen();
+            }
+            _ => leave(app, leaving),
+        },
+        QuitChoice::Cancel => {
+            app.pop_screen();
+        }
+    }
 }
 
 impl Actionable for QuitActions {
+    fn key_bindings() -> &'static [KeyBinding] {
+        const BINDINGS: &[KeyBinding] = &[
+            bind("y / n", "Quit / stay when nothing is uncommitted"),
+            bind("c", "Commit the open transaction and quit or open"),
+            bind("r", "Roll back the open transaction and quit or open"),
+            bind("n / Esc", "Cancel and stay in the session"),
+            bind("s", "Stay and start saving an in-memory database"),
+            bind("j / k", "Select a choice; Enter confirms it"),
+        ];
+        BINDINGS
+    }
+
     fn take_action(app: &mut App, key_event: KeyEvent) {
+        // the Ctrl-Q / Ctrl-C that opened the dialog arrives here too and must not answer it
+        if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+            return;
+        }
+        let Some(Screen::Exiting(quit_screen)) = app.screens.last_mut() else {
+            return;
+        };
+
+        let leaving = quit_screen.leaving.clone();
+        if quit_screen.unsaved && key_event.code == KeyCode::Char('s') {
+            save_first(app);
+            return;
+        }
+        if quit_screen.pending_statements.is_none() {
+            match key_event.code {
+                KeyCode::Char('y') => leave(app, leaving),
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    app.pop_screen();
+                }
+                _ => {}
+            }
+            return;
+        }
+
         match key_event.code {
-            KeyCode::Char('y') => app.exit(),
-            KeyCode::Char('n') => app.screen = Screen::Terminal(TerminalScreen::new()),
+            KeyCode::Char('j') | KeyCode::Down | KeyCode::Tab => quit_screen.next_choice(),
+            KeyCode::Char('k') | KeyCode::Up | KeyCode::BackTab => quit_screen.previous_choice(),
+            KeyCode::Enter => {
+                let choice = quit_screen.selected();
+                quit_with(app, choice, leaving);
+            }
+            KeyCode::Char('c') => quit_with(app, QuitChoice::CommitAndQuit, leaving),
+            KeyCode::Char('r') => quit_with(app, QuitChoice::RollbackAndQuit, leaving),
+            KeyCode::Char('n') | KeyCode::Esc => quit_with(app, QuitChoice::Cancel, leaving),
             _ => {}
         }
     }
@@ -1,36 +1,228 @@
 use crate::{
-    actions::Actionable,
+    actions::{Actionable, KeyBinding, bind},
     app::App,
-    ui::screens::{Screen, terminal_screen::TerminalScreen},
+    ui::clipboard::copy_to_clipboard,
+    ui::screens::{Screen, results_screen::PromptKind},
 };
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-#[derive(Debug)]
-pub enum ResultActions {
-    MoveCursorRight,
-    MoveCursorLeft,
-    MoveCursorUp,
-    MoveCursorDown,
-    ExitResults,
+fn copy(app: &mut App, description: &str, text: Option<String>) {
+    let Some(text) = text else {
+        return;
+    };
+    match copy_to_clipboard(&text) {
+        Ok(()) => app
+            .notifications
+            .notify("Copied", &format!("{} copied to clipboard.", description)),
+        Err(e) => app.notifications.error(e.into()),
+    }
+}
+
+/// keeps the active tab's column arrangement for the next run of its query
+fn remember_layout(app: &mut App) {
+    if let Some(results_screen) = app.results.current()
+        && let Some(table_view) = &results_screen.table_view
+    {
+        app.column_layouts
+            .insert(results_screen.query.clone(), table_view.layout.clone());
+    }
 }
 
+const INSPECTOR_PAGE: usize = 10;
+
+#[derive(Debug)]
+pub enum ResultActions {}
+
 impl Actionable for ResultActions {
+    fn key_bindings() -> &'static [KeyBinding] {
+        const BINDINGS: &[KeyBinding] = &[
+            bind("j / k", "Move to the next / previous row"),
+            bind("h / l", "Move to the previous / next column"),
+            bind("PageDown / PageUp", "Move one page down / up"),
+            bind("Ctrl-D / Ctrl-U", "Move half a page down / up"),
+            bind("g / G", "Go to the first / last row (25G goes to row 25)"),
+            bind("0 / $", "Go to the first / last column"),
+            bind(":", "Jump to a row number"),
+            bind("<count>", "Repeat the next motion, as in 25j"),
+            bind("s", "Cycle the sort on the selected column"),
+            bind("/", "Search all cells"),
+            bind("n / N", "Jump to the next / previous match"),
+            bind("f", "Filter rows by text or a condition like amount > 100"),
+            bind("Enter", "Inspect the selected cell"),
+            bind("w", "Save a blob to a file (inspector)"),
+            bind("x / X", "Hide the selected column / show all columns"),
+            bind("H / L", "Move the selected column left / right"),
+            bind("p", "Pin or unpin columns up to the selected one"),
+            bind("+ / -", "Grow / shrink the selected column"),
+            bind("y", "Copy the selected cell"),
+            bind("Y / J", "Copy the selected row as TSV / JSON"),
+            bind("C", "Copy the selected column"),
+            bind(
+                "Tab / Shift-Tab",
+                "Switch to the next / previous result tab",
+            ),
+            bind("Ctrl-W", "Close the current result tab"),
+            bind("q / Esc", "Return to the terminal"),
+        ];
+        BINDINGS
+    }
+
     fn take_action(app: &mut App, key_event: KeyEvent) {
-        if let Screen::Results(results_screen) = &mut app.screen {
+        if !matches!(app.screens.last(), Some(Screen::Results)) {
+            return;
+        }
+        // Ctrl shortcuts are handled globally first, so only the ones below may act on them
+        let control = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        if let Some(results_screen) = app.results.current_mut() {
+            if let Some(inspector) = &mut results_screen.inspector {
+                if let Some(path) = inspector.confirm_overwrite.take() {
+                    match key_event.code {
+                        KeyCode::Char('y') if !control => match inspector.write_blob(path) {
+                            Ok(path) => app
+                                .notifications
+                                .notify("Saved", &format!("Blob written to {}", path)),
+                            Err(e) => app.notifications.error(e.into()),
+                        },
+                        KeyCode::Char('n') | KeyCode::Esc => {}
+                        _ => inspector.confirm_overwrite = Some(path),
+                    }
+                    return;
+                }
+                if let Some(path) = &mut inspector.save_prompt {
+                    match key_event.code {
+                        KeyCode::Char(to_insert) if !control => path.push(to_insert),
+                        KeyCode::Backspace => {
+                            path.pop();
+                        }
+                        KeyCode::Enter => match inspector.save_blob() {
+                            Ok(Some(path)) => app
+                                .notifications
+                                .notify("Saved", &format!("Blob written to {}", path)),
+                            Ok(None) => {}
+                            Err(e) => app.notifications.error(e.into()),
+                        },
+                        KeyCode::Esc => inspector.save_prompt = None,
+                        _ => {}
+                    }
+                    return;
+                }
+
+                match key_event.code {
+                    KeyCode::Char(_) if control => {}
+                    KeyCode::Char('j') | KeyCode::Down => inspector.scroll_down(1),
+                    KeyCode::Char('k') | KeyCode::Up => inspector.scroll_up(1),
+                    KeyCode::PageDown => inspector.scroll_down(INSPECTOR_PAGE),
+                    KeyCode::PageUp => inspector.scroll_up(INSPECTOR_PAGE),
+                    KeyCode::Char('w') => inspector.open_save_prompt(),
+                    KeyCode::Char('q') | KeyCode::Esc => results_screen.inspector = None,
+                    _ => {}
+                }
+                return;
+            }
+
+            // an open search or filter prompt captures all typing
+            if let Some(prompt) = &mut results_screen.prompt {
+                match key_event.code {
+                    KeyCode::Char(to_insert) if !control => prompt.input.push(to_insert),
+                    KeyCode::Backspace => {
+                        prompt.input.pop();
+                    }
+                    KeyCode::Enter => results_screen.submit_prompt(),
+                    KeyCode::Esc => results_screen.prompt = None,
+                    _ => {}
+                }
+                return;
+            }
+
+            // digits build up a count for the next motion; a leading 0 is a motion itself
+            if let KeyCode::Char(digit @ '0'..='9') = key_event.code
+                && !control
+                && (digit != '0' || results_screen.count.is_some())
+            {
+                results_screen.push_count_digit(digit.to_digit(10).unwrap_or(0));
+                return;
+            }
+            let count = results_screen.count.take();
+            let repeat = count.unwrap_or(1).min(isize::MAX as usize) as isize;
+
             //handle table navigation if the tableview is loaded
             if let Some(table_view) = &mut results_screen.table_view {
                 match key_event.code {
-                    KeyCode::Char('j') | KeyCode::Down => table_view.next_row(),
-                    KeyCode::Char('k') | KeyCode::Up => table_view.previous_row(),
-                    KeyCode::Char('h') | KeyCode::Left => table_view.previous_column(),
-                    KeyCode::Char('l') | KeyCode::Right => table_view.next_column(),
+                    KeyCode::Char('d') if control => table_view.half_page_down(),
+                    KeyCode::Char('u') if control => table_view.half_page_up(),
+                    KeyCode::Char(_) if control => {}
+                    KeyCode::Char('j') | KeyCode::Down => table_view.move_rows(repeat),
+                    KeyCode::Char('k') | KeyCode::Up => table_view.move_rows(-repeat),
+                    KeyCode::Char('h') | KeyCode::Left => table_view.move_columns(-repeat),
+                    KeyCode::Char('l') | KeyCode::Right => table_view.move_columns(repeat),
+                    KeyCode::PageDown => table_view.page_down(),
+                    KeyCode::PageUp => table_view.page_up(),
+                    KeyCode::Char('g') | KeyCode::Home => match count {
+                        Some(row_number) => table_view.go_to_row(row_number),
+                        None => table_view.first_row(),
+                    },
+                    KeyCode::Char('G') | KeyCode::End => match count {
+                        Some(row_number) => table_view.go_to_row(row_number),
+                        None => table_view.last_row(),
+                    },
+                    KeyCode::Char('0') => table_view.first_column(),
+                    KeyCode::Char('$') => table_view.last_column(),
+                    KeyCode::Char('s') => table_view.cycle_sort(),
+                    KeyCode::Char('n') => table_view.next_match(),
+                    KeyCode::Char('N') => table_view.previous_match(),
+                    KeyCode::Char('x') => table_view.hide_column(),
+                    KeyCode::Char('X') => table_view.show_all_columns(),
+                    KeyCode::Char('H') => table_view.move_column_left(),
+                    KeyCode::Char('L') => table_view.move_column_right(),
+                    KeyCode::Char('p') => table_view.toggle_pin(),
+                    KeyCode::Char('+') | KeyCode::Char('=') => {
+                        table_view.resize_column(true, app.max_column_width)
+                    }
+                    KeyCode::Char('-') => table_view.resize_column(false, app.max_column_width),
+                    KeyCode::Char('y') => {
+                        let text = table_view.selected_cell_text();
+                        return copy(app, "Cell", text);
+                    }
+                    KeyCode::Char('Y') => {
+                        let text = table_view.selected_row_tsv();
+                        return copy(app, "Row", text);
+                    }
+                    KeyCode::Char('J') => {
+                        let text = table_view.selected_row_json();
+                        return copy(app, "Row JSON", text);
+                    }
+                    KeyCode::Char('C') => {
+                        let text = table_view.selected_column_text();
+                        return copy(app, "Column", text);
+                    }
                     _ => {}
                 }
             }
             // non navigation related functionality
             match key_event.code {
+                KeyCode::Char('w') if control => {
+                    remember_layout(app);
+                    app.results.close_tab();
+                    if app.results.is_empty() {
+                        app.pop_screen();
+                    }
+                }
+                KeyCode::Char(_) if control => {}
+                KeyCode::Char('/') => results_screen.open_prompt(PromptKind::Search),
+                KeyCode::Char('f') => results_screen.open_prompt(PromptKind::Filter),
+                KeyCode::Char(':') => results_screen.open_prompt(PromptKind::JumpToRow),
+                KeyCode::Enter => results_screen.open_inspector(),
+                KeyCode::Tab => {
+                    remember_layout(app);
+                    app.results.next_tab();
+                }
+                KeyCode::BackTab => {
+                    remember_layout(app);
+                    app.results.previous_tab();
+                }
                 KeyCode::Char('q') | KeyCode::Esc => {
-                    app.screen = Screen::Terminal(TerminalScreen::new());
+                    remember_layout(app);
+                    app.pop_screen();
                 }
                 _ => {}
             }
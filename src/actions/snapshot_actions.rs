@@ -0,0 +1,89 @@
+use crate::{
+    actions::{Actionable, KeyBinding, bind},
+    app::App,
+    model::policy::WritePolicy,
+    ui::screens::{Screen, snapshot_screen::SnapshotScreen},
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug)]
+pub enum SnapshotActions {}
+
+fn restore(app: &mut App) {
+    let Some(Screen::Snapshots(snapshot_screen)) = app.screens.last_mut() else {
+        return;
+    };
+    snapshot_screen.confirm_restore = false;
+    let Some(path) = snapshot_screen
+        .selected()
+        .map(|snapshot| snapshot.path.clone())
+    else {
+        return;
+    };
+
+    match app.session.restore_snapshot(&path) {
+        Ok(()) => {
+            app.notifications.notify(
+                "Restored",
+                &format!("Database restored from {}", path.display()),
+            );
+            // restoring takes a snapshot of its own, so the list has changed
+            if let Ok(snapshots) = app.session.list_snapshots()
+                && let Some(Screen::Snapshots(snapshot_screen)) = app.screens.last_mut()
+            {
+                *snapshot_screen = SnapshotScreen::new(snapshots);
+            }
+        }
+        Err(e) => app.notifications.error(e),
+    }
+}
+
+impl Actionable for SnapshotActions {
+    fn key_bindings() -> &'static [KeyBinding] {
+        const BINDINGS: &[KeyBinding] = &[
+            bind("j / k", "Select the next / previous snapshot"),
+            bind(
+                "r / Enter",
+                "Restore the selected snapshot over the database",
+            ),
+            bind("o", "Open the selected snapshot read-only"),
+            bind("q / Esc", "Return to the terminal"),
+        ];
+        BINDINGS
+    }
+
+    fn take_action(app: &mut App, key_event: KeyEvent) {
+        let Some(Screen::Snapshots(snapshot_screen)) = app.screens.last_mut() else {
+            return;
+        };
+
+        if snapshot_screen.confirm_restore {
+            // Ctrl shortcuts are handled globally first, so Ctrl-Y must not overwrite the database
+            let control = key_event.modifiers.contains(KeyModifiers::CONTROL);
+            match key_event.code {
+                KeyCode::Char('y') if !control => restore(app),
+                KeyCode::Char('n') | KeyCode::Esc => snapshot_screen.confirm_restore = false,
+                _ => {}
+            }
+            return;
+        }
+
+        match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => snapshot_screen.next(),
+            KeyCode::Char('k') | KeyCode::Up => snapshot_screen.previous(),
+            KeyCode::Char('r') | KeyCode::Enter if snapshot_screen.selected().is_some() => {
+                snapshot_screen.confirm_restore = true
+            }
+            KeyCode::Char('o') => {
+                if let Some(snapshot) = snapshot_screen.selected() {
+                    let path = snapshot.path.display().to_string();
+                    app.open_database(path, WritePolicy::ReadOnly);
+                }
+            }
+            KeyCode::Char('q') | KeyCode::Esc => {
+                app.pop_screen();
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    actions::{Actionable, KeyBinding, bind},
+    app::App,
+    ui::screens::Screen,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug)]
+pub enum StorageActions {}
+
+impl Actionable for StorageActions {
+    fn key_bindings() -> &'static [KeyBinding] {
+        const BINDINGS: &[KeyBinding] = &[
+            bind("j / k", "Select the next / previous object"),
+            bind("s", "Sort by the next column"),
+            bind("o", "Reverse the sort"),
+            bind("r", "Measure again"),
+            bind("q / Esc", "Return to the terminal"),
+        ];
+        BINDINGS
+    }
+
+    fn take_action(app: &mut App, key_event: KeyEvent) {
+        // global shortcuts arrive here too; Ctrl-S must not sort and Ctrl-R must not measure
+        if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+            return;
+        }
+        let Some(Screen::Storage(storage_screen)) = app.screens.last_mut() else {
+            return;
+        };
+
+        match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => storage_screen.next(),
+            KeyCode::Char('k') | KeyCode::Up => storage_screen.previous(),
+            KeyCode::Char('s') => storage_screen.next_sort_column(),
+            KeyCode::Char('o') => storage_screen.reverse_sort(),
+            KeyCode::Char('r') => match app.session.storage_report() {
+                Ok(report) => storage_screen.refresh(report),
+                Err(e) => app.notifications.error(e),
+            },
+            KeyCode::Char('q') | KeyCode::Esc => {
+                app.pop_screen();
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,31 +1,253 @@
 use crate::{
-    actions::Actionable,
+    actions::{Actionable, KeyBinding, bind},
     app::App,
+    model::{
+        file_browser::expand_home, guard::GuardMode, integrity::CheckKind, query_plan::build_tree,
+    },
     ui::{
-        screens::{Screen, results_screen::ResultsScreen},
+        clipboard::copy_to_clipboard,
+        screens::{
+            Screen, check_screen::CheckScreen, confirm_screen::ConfirmScreen,
+            info_screen::InfoScreen, pragma_screen::PragmaScreen,
+            query_plan_screen::QueryPlanScreen, results_screen::ResultsScreen,
+            snapshot_screen::SnapshotScreen, storage_screen::StorageScreen,
+        },
         table::TableView,
+        utils::format_timestamp,
     },
 };
-use color_eyre::Section;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 #[derive(Debug)]
-pub enum TerminalActions {
-    MoveHistoryForward,
-    MoveHistoryBackward,
-    MoveCursorRight,
-    MoveCursorLeft,
-    InputCharacter,
-    Backspace,
-    Delete,
-    EnterCommand,
+pub enum TerminalActions {}
+
+fn log_line(app: &mut App, line: String) {
+    app.terminal.add_log_line(line);
+}
+
+fn explain_query(app: &mut App, query: &str) -> Option<Screen> {
+    match app.session.explain_query_plan(query) {
+        Ok(rows) => Some(Screen::QueryPlan(QueryPlanScreen::new(
+            query.to_string(),
+            build_tree(&rows),
+        ))),
+        Err(e) => {
+            log_line(app, format!("Error: {}", e));
+            app.notifications.error(e);
+            None
+        }
+    }
+}
+
+fn undo_last_commit(app: &mut App) {
+    match app.session.undo_last_commit() {
+        Ok((entry, conflicts)) if conflicts.is_empty() => log_line(
+            app,
+            format!(
+                "Staged the undo of the commit from {}; commit it to keep it or roll it back",
+                format_timestamp(entry.committed_at)
+            ),
+        ),
+        Ok((entry, conflicts)) => {
+            log_line(
+                app,
+                format!(
+                    "Could not undo the commit from {}; it conflicts with later changes:",
+                    format_timestamp(entry.committed_at)
+                ),
+            );
+            for conflict in conflicts {
+                log_line(app, format!("  {}", conflict));
+            }
+        }
+        Err(e) => {
+            log_line(app, format!("Error: {}", e));
+            app.notifications.error(e);
+        }
+    }
+}
+
+/// starts a check in the background and opens the screen that follows it
+pub fn start_check(app: &mut App, kind: CheckKind) -> Option<Screen> {
+    match app.session.start_check(kind) {
+        Ok(run) => Some(Screen::Check(CheckScreen::new(kind, run))),
+        Err(e) => {
+            log_line(app, format!("Error: {}", e));
+            app.notifications.error(e);
+            None
+        }
+    }
+}
+
+/// handles rivet's own `.command` syntax, which never reaches sqlite directly
+fn execute_dot_command(app: &mut App, command: &str) -> Option<Screen> {
+    let (name, argument) = command
+        .split_once(char::is_whitespace)
+        .unwrap_or((command, ""));
+
+    match name {
+        "plan" => explain_query(app, argument.trim()),
+        "copy" => {
+            let terminal_screen = &app.terminal;
+            let text = match argument.trim() {
+                "log" => Some(Vec::from(terminal_screen.displayed_lines.clone()).join("\n")),
+                _ => terminal_screen.last_output(),
+            };
+            match text.map(|text| copy_to_clipboard(&text)) {
+                Some(Ok(())) => log_line(app, "Copied to clipboard".to_string()),
+                Some(Err(e)) => app.notifications.error(e.into()),
+                None => log_line(app, "Nothing to copy".to_string()),
+            }
+            None
+        }
+        "snapshots" => match app.session.list_snapshots() {
+            Ok(_) if app.session.snapshots.is_none() => {
+                log_line(
+                    app,
+                    "Snapshots are off; start rivet with --snapshot-dir to take them".to_string(),
+                );
+                None
+            }
+            Ok(snapshots) => Some(Screen::Snapshots(SnapshotScreen::new(snapshots))),
+            Err(e) => {
+                log_line(app, format!("Error: {}", e));
+                app.notifications.error(e);
+                None
+            }
+        },
+        "info" => match app.session.database_info() {
+            Ok(info) => Some(Screen::Info(InfoScreen::new(info))),
+            Err(e) => {
+                log_line(app, format!("Error: {}", e));
+                app.notifications.error(e);
+                None
+            }
+        },
+        "check" => {
+            let kind = match argument.trim() {
+                "" => CheckKind::Integrity,
+                "quick" => CheckKind::Quick,
+                "fk" => CheckKind::ForeignKeys,
+                _ => {
+                    log_line(app, "Usage: .check [quick|fk]".to_string());
+                    return None;
+                }
+            };
+            start_check(app, kind)
+        }
+        "pragmas" => Some(Screen::Pragmas(PragmaScreen::new(&app.session))),
+        "storage" => match app.session.storage_report() {
+            Ok(report) => Some(Screen::Storage(StorageScreen::new(report))),
+            Err(e) => {
+                log_line(app, format!("Error: {}", e));
+                app.notifications.error(e);
+                None
+            }
+        },
+        "open" => {
+            match argument.trim() {
+                "" => app.show_open_screen(),
+                path => {
+                    let policy = app.policy;
+                    app.open_database(expand_home(path).display().to_string(), policy);
+                }
+            }
+            None
+        }
+        "save" => {
+            match argument.trim() {
+                "" => log_line(app, "Usage: .save <path>".to_string()),
+                path => {
+                    let path = expand_home(path).display().to_string();
+                    match app.session.save_as(&path) {
+                        Ok(()) => log_line(app, format!("Saved a copy to {}", path)),
+                        Err(e) => {
+                            log_line(app, format!("Error: {}", e));
+                            app.notifications.error(e);
+                        }
+                    }
+                }
+            }
+            None
+        }
+        "undo" => {
+            undo_last_commit(app);
+            None
+        }
+        "timer" => {
+            match argument.trim() {
+                "on" => app.show_timer = true,
+                "off" => app.show_timer = false,
+                _ => {
+                    log_line(app, "Usage: .timer on|off".to_string());
+                    return None;
+                }
+            }
+            let state = if app.show_timer { "on" } else { "off" };
+            log_line(app, format!("Timer {}", state));
+            None
+        }
+        _ => {
+            log_line(app, format!("Unknown command: .{}", name));
+            None
+        }
+    }
+}
+
+/// runs a statement that returns no rows and logs its outcome
+pub fn run_statement(app: &mut App, query: &str) {
+    match app.session.execute(query) {
+        Ok(changes) => {
+            log_line(app, format!("{} changes.", changes));
+            if app.show_timer
+                && let Some(stats) = &app.session.last_stats
+            {
+                app.terminal.add_log_line(stats.summary());
+            }
+        }
+        Err(e) => {
+            log_line(app, format!("Error: {}", e));
+            app.notifications.error(e);
+        }
+    }
+}
+
+/// runs a query and opens its rows in a new result tab, logging when there are none
+pub fn show_results(app: &mut App, query: String) -> Option<Screen> {
+    let column_names: Vec<String> = app.session.extract_column_names(&query).unwrap_or_default();
+    match app.session.select(&query) {
+        Ok(data) => {
+            if app.show_timer
+                && let Some(stats) = &app.session.last_stats
+            {
+                app.terminal.add_log_line(stats.summary());
+            }
+            if data.is_empty() {
+                log_line(app, "Query returned 0 rows".to_string());
+                None
+            } else {
+                let mut results_screen = ResultsScreen::new(query.clone());
+                let mut table_view = TableView::new(column_names, data);
+                // re-running a query keeps the column arrangement from last time
+                if let Some(layout) = app.column_layouts.get(&query) {
+                    table_view.apply_layout(layout.clone());
+                }
+                results_screen.table_view = Some(table_view);
+                results_screen.stats = app.session.last_stats.clone();
+                app.results.open(results_screen, app.results_history);
+                Some(Screen::Results)
+            }
+        }
+        Err(e) => {
+            log_line(app, format!("Error: {}", e));
+            app.notifications.error(e);
+            None
+        }
+    }
 }
 
 fn execute_command(app: &mut App) -> Option<Screen> {
-    // This command can only be executed from the Terminal screen
-    let Screen::Terminal(terminal_screen) = &mut app.screen else {
-        return None;
-    };
+    let terminal_screen = &mut app.terminal;
 
     let query = terminal_screen.input.to_string();
     if query.is_empty() {
@@ -35,72 +257,128 @@ fn execute_command(app: &mut App) -> Option<Screen> {
 
     terminal_screen.add_log_line(format!("> {}", query));
 
+    if let Some(command) = query.strip_prefix('.') {
+        terminal_screen.add_command();
+        return execute_dot_command(app, command);
+    }
+
     // Toggle operation for select vs other operations
     if query
         .split_whitespace()
         .next()
         .is_some_and(|x| x.to_ascii_uppercase().eq("SELECT"))
     {
-        let column_names: Vec<String> =
-            app.session.extract_column_names(&query).unwrap_or_default();
-        match app.session.select(&query) {
-            Ok(data) => {
-                if data.is_empty() {
-                    terminal_screen.add_log_line("Query returned 0 rows".to_string());
-                    terminal_screen.add_command();
-                    None
-                } else {
-                    let mut results_screen = ResultsScreen::new();
-                    results_screen.table_view = Some(TableView::new(column_names, data));
-                    terminal_screen.add_command();
-                    Some(Screen::Results(results_screen))
-                }
-            }
-            Err(e) => {
-                terminal_screen.add_log_line(format!("Error: {}", e));
-                app.notifications.error(e);
-                terminal_screen.add_command();
-                None
-            }
-        }
+        terminal_screen.add_command();
+        show_results(app, query)
     } else {
-        match app.session.execute(&query) {
-            Ok(changes) => {
-                terminal_screen.add_log_line(format!("{} changes.", changes));
+        terminal_screen.add_command();
+        match app.session.assess(&query) {
+            Some(warning) if app.session.guard.mode == GuardMode::Block => {
+                log_line(app, format!("Blocked: {}", warning.risk.summary));
+                None
             }
-            Err(e) => {
-                terminal_screen.add_log_line(format!("Error: {}", e));
-                app.notifications.error(e);
+            Some(warning) => Some(Screen::Confirm(ConfirmScreen::new(query, warning))),
+            None => {
+                run_statement(app, &query);
+                None
             }
         }
-        terminal_screen.add_command();
-        None
     }
 }
 
 impl Actionable for TerminalActions {
+    fn key_bindings() -> &'static [KeyBinding] {
+        const BINDINGS: &[KeyBinding] = &[
+            bind("Enter", "Execute the current statement"),
+            bind("Up / Down", "Move through the command history"),
+            bind("Left / Right", "Move the cursor"),
+            bind("Tab", "Complete table, column, function and keyword names"),
+            bind("Tab / Shift-Tab", "Select the next / previous suggestion"),
+            bind("Enter", "Insert the selected suggestion"),
+            bind("Esc", "Close the completion popup"),
+            bind("Ctrl-E", "Show the query plan for the current input"),
+            bind("Ctrl-T", "Reopen the result tabs from earlier queries"),
+            bind(".plan <query>", "Show the query plan for a query"),
+            bind(".copy [log]", "Copy the last output, or the whole log"),
+            bind(".timer on|off", "Show or hide statistics after each query"),
+            bind(".snapshots", "Browse, restore or open pre-commit snapshots"),
+            bind(".undo", "Stage the reverse of the last commit"),
+            bind(
+                ".info",
+                "Show a summary of the database's settings and size",
+            ),
+            bind(
+                ".check [quick|fk]",
+                "Run integrity_check, quick_check or foreign_key_check",
+            ),
+            bind(".pragmas", "View and change PRAGMA settings"),
+            bind(".storage", "Show how much space each table and index takes"),
+            bind(".open [path]", "Open another database, or browse for one"),
+            bind(".save <path>", "Save a copy of the database to a new file"),
+        ];
+        BINDINGS
+    }
+
     fn take_action(app: &mut App, key_event: KeyEvent) {
-        if let Screen::Terminal(terminal_screen) = &mut app.screen {
+        let terminal_screen = &mut app.terminal;
+        // Ctrl shortcuts arrive here after their global action and must not be typed
+        let control = key_event.modifiers.contains(KeyModifiers::CONTROL);
+
+        // the completion popup takes navigation keys while it is open
+        if terminal_screen.completion.is_some() {
             match key_event.code {
-                KeyCode::Up => terminal_screen.decrement_history(),
-                KeyCode::Down => terminal_screen.increment_history(),
-                KeyCode::Left => terminal_screen.move_cursor_left(),
-                KeyCode::Right => terminal_screen.move_cursor_right(),
-                KeyCode::Char(to_insert) => terminal_screen.enter_char(to_insert),
-                KeyCode::Backspace => terminal_screen.delete_char(),
-                KeyCode::Delete => {
-                    //TODO: resolve issues with delete turning into backspace at end of
-                    //line
-                    terminal_screen.move_cursor_right();
+                KeyCode::Char(_) if control => terminal_screen.close_completion(),
+                KeyCode::Tab | KeyCode::Down => return terminal_screen.next_completion(),
+                KeyCode::BackTab | KeyCode::Up => return terminal_screen.previous_completion(),
+                KeyCode::Enter => return terminal_screen.accept_completion(),
+                KeyCode::Esc => return terminal_screen.close_completion(),
+                KeyCode::Char(to_insert) => {
+                    terminal_screen.enter_char(to_insert);
+                    return terminal_screen.open_completion(&app.session.schema);
+                }
+                KeyCode::Backspace => {
                     terminal_screen.delete_char();
+                    return terminal_screen.open_completion(&app.session.schema);
                 }
-                KeyCode::Enter => {
-                    if let Some(new_screen) = execute_command(app) {
-                        app.screen = new_screen;
-                    }
+                _ => terminal_screen.close_completion(),
+            }
+        }
+
+        if key_event.code == KeyCode::Char('e') && control {
+            let query = terminal_screen.input.trim().to_string();
+            if let Some(new_screen) = explain_query(app, &query) {
+                app.push_screen(new_screen);
+            }
+            return;
+        }
+
+        if key_event.code == KeyCode::Char('t') && control {
+            if !app.results.is_empty() {
+                app.push_screen(Screen::Results);
+            }
+            return;
+        }
+
+        match key_event.code {
+            KeyCode::Tab => terminal_screen.open_completion(&app.session.schema),
+            KeyCode::Up => terminal_screen.decrement_history(),
+            KeyCode::Down => terminal_screen.increment_history(),
+            KeyCode::Left => terminal_screen.move_cursor_left(),
+            KeyCode::Right => terminal_screen.move_cursor_right(),
+            KeyCode::Char(to_insert) if !control => terminal_screen.enter_char(to_insert),
+            KeyCode::Backspace => terminal_screen.delete_char(),
+            KeyCode::Delete => {
+                //TODO: resolve issues with delete turning into backspace at end of
+                //line
+                terminal_screen.move_cursor_right();
+                terminal_screen.delete_char();
+            }
+            KeyCode::Enter => {
+                if let Some(new_screen) = execute_command(app) {
+                    app.push_screen(new_screen);
                 }
-                _ => {}
             }
+            _ => {}
         }
     }
 }
@@ -1,56 +1,195 @@
 use crate::actions::Actions;
 use crate::model::notifications::NotificationList;
-use crate::model::sql_session::SqlSession;
-use crate::ui::screens::{Screen, terminal_screen::TerminalScreen};
+use crate::model::open::{CreateMode, OpenError};
+use crate::model::policy::WritePolicy;
+use crate::model::recent::RecentFiles;
+use crate::model::sql_session::{SessionSettings, SqlSession};
+use crate::ui::screens::open_screen::OpenScreen;
+use crate::ui::screens::quit_screen::{Leaving, QuitScreen};
+use crate::ui::screens::{Screen, results_tabs::ResultTabs, terminal_screen::TerminalScreen};
+use crate::ui::table::ColumnLayout;
 use crate::ui::{themes::ColorPalette, ui};
 use crossterm::event::{self, Event, KeyEventKind};
 use ratatui::{DefaultTerminal, Frame};
+use signal_hook::consts::{SIGHUP, SIGTERM};
+use std::collections::HashMap;
 use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 pub const TOOL_NAME: &str = "rivet";
+/// how long to wait for input before checking for signals
+const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// how often a running check's progress is redrawn
+const CHECK_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 pub struct App {
+    /// the open database's path, empty until one is opened
     pub sql_path: String,
     pub session: SqlSession,
-    pub screen: Screen,
+    /// the terminal lives for the whole session, underneath every other screen
+    pub terminal: TerminalScreen,
+    /// screens layered over the terminal; the last one receives input
+    pub screens: Vec<Screen>,
+    /// result sets from earlier queries, kept so they can be revisited without re-running
+    pub results: ResultTabs,
+    /// how many result sets are kept before the oldest is dropped
+    pub results_history: usize,
     pub notifications: NotificationList,
     pub theme: ColorPalette,
+    pub show_timer: bool,
+    pub max_column_width: u16,
+    /// column arrangements keyed by the query text that produced them
+    pub column_layouts: HashMap<String, ColumnLayout>,
+    /// the policy databases are opened with from the open screen
+    pub policy: WritePolicy,
+    /// whether opening a missing file creates it
+    pub create: CreateMode,
+    pub recent: RecentFiles,
+    /// applied to every database opened during the run
+    pub settings: SessionSettings,
+    /// printed to stderr once the terminal is restored, for what could not be shown on screen
+    pub exit_message: Option<String>,
     exit: bool,
 }
 
 impl App {
-    pub fn new(sql_path: String, read_only: bool) -> Self {
-        let sql_session = SqlSession::new(sql_path.clone(), read_only);
-        App {
-            sql_path,
+    /// opens `sql_path`, or an in-memory copy of it with `scratch`; without one, or when it
+    /// fails to open, the open screen takes over with an empty in-memory database standing in
+    /// until a file is opened
+    pub fn new(
+        sql_path: Option<String>,
+        scratch: bool,
+        policy: WritePolicy,
+        create: CreateMode,
+        settings: SessionSettings,
+        max_column_width: u16,
+        results_history: usize,
+    ) -> Result<Self, OpenError> {
+        let opened = match &sql_path {
+            _ if scratch => Some(SqlSession::scratch(sql_path.clone(), policy)),
+            Some(sql_path) => Some(SqlSession::new(
+                sql_path.clone(),
+                policy,
+                create == CreateMode::Always,
+            )),
+            None => None,
+        };
+        let (sql_session, open_error) = match opened {
+            Some(Ok(sql_session)) => (sql_session, None),
+            Some(Err(e)) => (
+                SqlSession::new(":memory:".to_string(), policy, true)?,
+                Some(e),
+            ),
+            None => (SqlSession::new(":memory:".to_string(), policy, true)?, None),
+        };
+        let mut app = App {
+            sql_path: String::new(),
             session: sql_session,
-            screen: Screen::Terminal(TerminalScreen::new()),
+            terminal: TerminalScreen::new(),
+            screens: Vec::new(),
+            results: ResultTabs::default(),
+            results_history,
             notifications: NotificationList::new(),
             theme: ColorPalette::nord(),
+            show_timer: true,
+            max_column_width,
+            column_layouts: HashMap::new(),
+            policy,
+            create,
+            recent: RecentFiles::load(),
+            settings,
+            exit_message: None,
             exit: false,
+        };
+        app.session.apply_settings(&app.settings);
+        match (sql_path, open_error) {
+            (_, None) if scratch => app.sql_path = ":memory:".to_string(),
+            (Some(sql_path), None) => app.opened(sql_path),
+            (sql_path, error) => {
+                let sql_path = sql_path.unwrap_or_default();
+                app.push_screen(Screen::Open(OpenScreen::new(
+                    sql_path.clone(),
+                    policy,
+                    false,
+                    app.recent.paths.clone(),
+                )));
+                if let Some(error) = error {
+                    app.show_open_error(sql_path, policy, error);
+                }
+            }
         }
+        Ok(app)
     }
 
     /// runs the application's main loop until the user quits
     pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        let terminate = Arc::new(AtomicBool::new(false));
+        let hangup = Arc::new(AtomicBool::new(false));
+        signal_hook::flag::register(SIGTERM, Arc::clone(&terminate))?;
+        signal_hook::flag::register(SIGHUP, Arc::clone(&hangup))?;
+
         while !self.exit {
             terminal.draw(|frame| self.draw(frame))?;
 
             // Handle user actions
-            match event::read()? {
-                Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                    <Actions>::handle_actions(self, key_event);
+            // a running check is redrawn more often so its progress keeps moving
+            let poll_interval = match self.screens.last() {
+                Some(Screen::Check(check_screen)) if check_screen.is_running() => {
+                    CHECK_POLL_INTERVAL
                 }
-                _ => {}
+                _ => EVENT_POLL_INTERVAL,
             };
+            if event::poll(poll_interval)? {
+                match event::read()? {
+                    Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                        <Actions>::handle_actions(self, key_event);
+                    }
+                    _ => {}
+                };
+            }
+
+            if hangup.swap(false, Ordering::Relaxed) {
+                // the terminal is gone, so there is nobody left to ask about staged changes; what
+                // was thrown away is reported on stderr, which may still be a log file
+                if let Some(pending) = self.session.pending_statements() {
+                    let statements = if pending == 1 {
+                        "statement"
+                    } else {
+                        "statements"
+                    };
+                    let outcome = match self.session.rollback() {
+                        Ok(()) => "rolled back".to_string(),
+                        Err(e) => format!("left for sqlite to roll back ({})", e),
+                    };
+                    self.exit_message = Some(format!(
+                        "{}: hung up on {} with {} uncommitted {}, which were {}",
+                        TOOL_NAME, self.sql_path, pending, statements, outcome
+                    ));
+                }
+                self.exit();
+            }
+            if terminate.swap(false, Ordering::Relaxed) {
+                self.request_quit();
+            }
 
             self.notifications.remove_expired();
 
-            match &mut self.screen {
-                Screen::Terminal(_terminal_screen) => {}
-                Screen::Results(_results_screen) => {}
-                Screen::Help(_help_screen) => {}
-                Screen::Exiting(_quit_screen) => {}
+            match self.screens.last_mut() {
+                None => {}
+                Some(Screen::Results) => {}
+                Some(Screen::QueryPlan(_query_plan_screen)) => {}
+                Some(Screen::Help(_help_screen)) => {}
+                Some(Screen::Exiting(_quit_screen)) => {}
+                Some(Screen::Confirm(_confirm_screen)) => {}
+                Some(Screen::Snapshots(_snapshot_screen)) => {}
+                Some(Screen::Open(_open_screen)) => {}
+                Some(Screen::Info(_info_screen)) => {}
+                Some(Screen::Check(check_screen)) => check_screen.poll(),
+                Some(Screen::Pragmas(_pragma_screen)) => {}
+                Some(Screen::Storage(_storage_screen)) => {}
             }
         }
         Ok(())
@@ -61,6 +200,124 @@ impl App {
     }
 
     // App Specific Functionality.
+    pub fn push_screen(&mut self, screen: Screen) {
+        self.screens.push(screen);
+    }
+
+    /// closes the top screen, returning to whatever was beneath it
+    pub fn pop_screen(&mut self) -> Option<Screen> {
+        self.screens.pop()
+    }
+
+    /// quits straight away when nothing would be lost, otherwise asks what to do with the open
+    /// transaction or the unsaved in-memory database
+    fn request_quit(&mut self) {
+        let unsaved = self.session.has_unsaved_changes();
+        match self.session.pending_statements() {
+            None if !unsaved => self.exit(),
+            _ if matches!(self.screens.last(), Some(Screen::Exiting(_))) => {}
+            pending => self.push_screen(Screen::Exiting(QuitScreen::new(pending, unsaved))),
+        }
+    }
+
+    /// replaces the session with one on another database file, creating it only when
+    /// `--create` was given
+    pub fn open_database(&mut self, sql_path: String, policy: WritePolicy) {
+        let create = self.create == CreateMode::Always;
+        self.open_database_with(sql_path, policy, create);
+    }
+
+    /// replaces the session with one on another database file, keeping the session settings;
+    /// when it cannot be opened the open screen says why
+    pub fn open_database_with(&mut self, sql_path: String, policy: WritePolicy, create: bool) {
+        // switching closes the connection, so staged statements are settled first, as on quit,
+        // and an in-memory database is only dropped once the user says so
+        let pending = self.session.pending_statements();
+        let unsaved = self.session.has_unsaved_changes();
+        if pending.is_some() || unsaved {
+            if !matches!(self.screens.last(), Some(Screen::Exiting(_))) {
+                self.push_screen(Screen::Exiting(QuitScreen::before_opening(
+                    pending,
+                    unsaved,
+                    Leaving::Open {
+                        path: sql_path,
+                        policy,
+                        create,
+                    },
+                )));
+            }
+            return;
+        }
+        self.switch_database(sql_path, policy, create);
+    }
+
+    /// opens another database in place of the current one without asking about what the
+    /// current one would lose, for once the quit dialog has been answered
+    pub fn switch_database(&mut self, sql_path: String, policy: WritePolicy, create: bool) {
+        let mut session = match SqlSession::new(sql_path.clone(), policy, create) {
+            Ok(session) => session,
+            Err(e) => {
+                self.show_open_error(sql_path, policy, e);
+                return;
+            }
+        };
+        session.apply_settings(&self.settings);
+        self.session = session;
+        self.screens.clear();
+        self.results = ResultTabs::default();
+        self.opened(sql_path);
+        self.terminal
+            .add_log_line(format!("Opened {} ({})", self.sql_path, policy.name()));
+    }
+
+    /// records a newly opened database as the current one and in the recent list
+    fn opened(&mut self, sql_path: String) {
+        if Path::new(&sql_path).is_file()
+            && let Err(e) = self.recent.add(Path::new(&sql_path))
+        {
+            self.notifications.error(e);
+        }
+        self.sql_path = sql_path;
+    }
+
+    /// shows the open screen, browsing the directory of the open database
+    pub fn show_open_screen(&mut self) {
+        if matches!(self.screens.last(), Some(Screen::Open(_))) {
+            return;
+        }
+        let mut open_screen =
+            OpenScreen::new(String::new(), self.policy, true, self.recent.paths.clone());
+        if let Some(directory) = Path::new(&self.sql_path)
+            .parent()
+            .filter(|directory| directory.is_dir())
+        {
+            open_screen.browse(directory.to_path_buf());
+        }
+        self.push_screen(Screen::Open(open_screen));
+    }
+
+    /// explains on the open screen why `sql_path` could not be opened, offering to create it
+    /// when it is missing and `--create`/`--no-create` leave the choice to the user
+    fn show_open_error(&mut self, sql_path: String, policy: WritePolicy, error: OpenError) {
+        let offer_create = error.is_not_found()
+            && self.create == CreateMode::Ask
+            && policy != WritePolicy::ReadOnly;
+        if !matches!(self.screens.last(), Some(Screen::Open(_))) {
+            self.push_screen(Screen::Open(OpenScreen::new(
+                sql_path.clone(),
+                policy,
+                true,
+                self.recent.paths.clone(),
+            )));
+        }
+        if let Some(Screen::Open(open_screen)) = self.screens.last_mut() {
+            open_screen.path = sql_path;
+            open_screen.policy = policy;
+            open_screen.confirm_create = offer_create;
+            open_screen.error = Some(error.to_string());
+        }
+    }
+
     pub fn exit(&mut self) {
         self.exit = true;
     }
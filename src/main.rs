@@ -1,26 +1,124 @@
-use std::io;
+use std::io::{self, Write};
 mod app;
 use app::App;
 mod actions;
 mod model;
 mod ui;
 
+use model::guard::{GuardConfig, GuardMode, GuardRule};
+use model::open::CreateMode;
+use model::policy::WritePolicy;
+use model::snapshot::SnapshotConfig;
+use model::sql_session::SessionSettings;
+use std::path::PathBuf;
+
 use clap::Parser;
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
-    /// Path to the sqlite file
-    file: String,
+    /// Path to the sqlite file; without one rivet starts at the open screen
+    file: Option<String>,
+
+    /// Work on an in-memory database, starting from a copy of FILE when one is given
+    #[arg(long)]
+    scratch: bool,
 
-    /// Open in read-only mode
+    /// Open in read-only mode; the same as `--policy read-only`
     #[arg(short, long)]
     read_only: bool,
+
+    /// Create the database without asking when the file does not exist
+    #[arg(long, conflicts_with = "no_create")]
+    create: bool,
+
+    /// Never create a database; a missing file is reported instead
+    #[arg(long)]
+    no_create: bool,
+
+    /// Which statements may run
+    #[arg(long, value_enum, default_value_t = WritePolicy::Unrestricted)]
+    policy: WritePolicy,
+
+    /// What to do with statements that break a guard rule
+    #[arg(long, value_enum, default_value_t = GuardMode::Confirm)]
+    guard: GuardMode,
+
+    /// Which guard rules apply, comma separated (all of them by default)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    guard_rules: Vec<GuardRule>,
+
+    /// Unfiltered deletes and updates estimated to affect fewer rows than this run without asking
+    #[arg(long, default_value_t = 1)]
+    guard_min_rows: i64,
+
+    /// Copy the database into this directory before every commit
+    #[arg(long)]
+    snapshot_dir: Option<PathBuf>,
+
+    /// How many snapshots of a database are kept before the oldest are deleted
+    #[arg(long, default_value_t = 10)]
+    snapshot_keep: usize,
+
+    /// Don't keep committed changes in a journal next to the database, which disables undo
+    #[arg(long)]
+    no_journal: bool,
+
+    /// Widest a result column is drawn before its cells are truncated
+    #[arg(long, default_value_t = 50)]
+    max_column_width: u16,
+
+    /// How many result sets are kept as tabs before the oldest is dropped
+    #[arg(long, default_value_t = 10)]
+    results_history: usize,
 }
 
 fn main() -> io::Result<()> {
     let args = Args::parse();
+    let policy = if args.read_only {
+        WritePolicy::ReadOnly
+    } else {
+        args.policy
+    };
+
+    let create = if args.create {
+        CreateMode::Always
+    } else if args.no_create {
+        CreateMode::Never
+    } else {
+        CreateMode::Ask
+    };
+
+    let mut guard = GuardConfig {
+        mode: args.guard,
+        min_rows: args.guard_min_rows,
+        ..GuardConfig::default()
+    };
+    if !args.guard_rules.is_empty() {
+        guard.rules = args.guard_rules;
+    }
 
-    let mut app: App = App::new(args.file, args.read_only);
-    ratatui::run(|terminal| app.run(terminal))
+    let mut app: App = App::new(
+        args.file,
+        args.scratch,
+        policy,
+        create,
+        SessionSettings {
+            guard,
+            snapshots: args.snapshot_dir.map(|directory| SnapshotConfig {
+                directory,
+                keep: args.snapshot_keep,
+            }),
+            journal: !args.no_journal,
+        },
+        args.max_column_width,
+        args.results_history,
+    )
+    .map_err(io::Error::other)?;
+    ratatui::run(|terminal| app.run(terminal))?;
+    if let Some(message) = app.exit_message.take() {
+        // after a hangup stderr may be gone too, and there is nothing left to tell about that
+        let _ = writeln!(io::stderr(), "{}", message);
+    }
+    Ok(())
 }
@@ -0,0 +1,284 @@
+use std::fmt;
+
+/// what undoing reports when the session extension was not compiled in
+pub const UNDO_UNSUPPORTED: &str =
+    "This rivet was built without undo; rebuild it with --features undo";
+
+// commits are recorded and reversed with rusqlite's session extension bindings, which are only
+// compiled in with the `undo` feature
+#[cfg(feature = "undo")]
+pub use session::{Recorder, apply, invert};
+#[cfg(not(feature = "undo"))]
+pub use unsupported::{Recorder, apply, invert};
+
+/// why a change could not be applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+// only the session extension finds conflicts
+#[cfg_attr(not(feature = "undo"), allow(dead_code))]
+pub enum ConflictKind {
+    /// the row no longer holds the values the change expects
+    Changed,
+    /// the row the change updates or deletes is gone
+    Missing,
+    /// a row with the key the change inserts already exists
+    Exists,
+    /// applying the change would break a constraint
+    Constraint,
+    /// applying the changeset would leave this many foreign key violations
+    ForeignKey(i32),
+}
+
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    pub kind: ConflictKind,
+    pub table: String,
+    /// the primary key values of the row, comma separated
+    pub key: String,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ConflictKind::Changed => write!(
+                f,
+                "{} row {} was changed after the commit",
+                self.table, self.key
+            ),
+            ConflictKind::Missing => write!(
+                f,
+                "{} row {} was deleted after the commit",
+                self.table, self.key
+            ),
+            ConflictKind::Exists => write!(
+                f,
+                "{} row {} was inserted again after the commit",
+                self.table, self.key
+            ),
+            ConflictKind::Constraint => write!(
+                f,
+                "{} row {} would break a constraint",
+                self.table, self.key
+            ),
+            ConflictKind::ForeignKey(count) => {
+                write!(f, "{} foreign key constraints would be broken", count)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "undo")]
+mod session {
+    use super::{Conflict, ConflictKind};
+    use crate::model::value::display_value;
+    use color_eyre::eyre::Result;
+    use rusqlite::Connection;
+    use rusqlite::hooks::Action;
+    use rusqlite::session::{self, ChangesetItem, ConflictAction, ConflictType, Session};
+    use rusqlite::types::Value;
+    use std::sync::{Arc, Mutex};
+
+    /// records every change made to the main database through the session extension; only tables
+    /// with a PRIMARY KEY are recorded
+    pub struct Recorder {
+        session: Session<'static>,
+    }
+
+    impl Recorder {
+        /// # Safety
+        ///
+        /// the recorder borrows `connection` without a lifetime to show for it, so it must be
+        /// dropped before the connection is
+        pub unsafe fn start(connection: &Connection) -> Result<Self> {
+            let session = Session::new(connection)?;
+            // SAFETY: only the lifetime changes, and the caller keeps the connection alive
+            let mut session: Session<'static> = unsafe { std::mem::transmute(session) };
+            // no table name attaches every table, including ones created later
+            session.attach(None::<&str>)?;
+            Ok(Recorder { session })
+        }
+
+        /// the changes recorded so far, empty when nothing changed
+        pub fn changeset(&mut self) -> Result<Vec<u8>> {
+            let mut changeset = Vec::new();
+            self.session.changeset_strm(&mut changeset)?;
+            Ok(changeset)
+        }
+    }
+
+    /// a changeset that reverses `changeset`: inserts become deletes and updates swap old for new
+    pub fn invert(mut changeset: &[u8]) -> Result<Vec<u8>> {
+        let mut inverted = Vec::new();
+        session::invert_strm(&mut changeset, &mut inverted)?;
+        Ok(inverted)
+    }
+
+    /// describes the change `item` is on as a conflict of `kind`
+    fn describe_conflict(kind: ConflictKind, item: &ChangesetItem) -> rusqlite::Result<Conflict> {
+        let operation = item.op()?;
+        let table = operation.table_name().to_string();
+        let insert = operation.code() == Action::SQLITE_INSERT;
+        // inserts only carry new values, updates and deletes carry the old key
+        let mut key = Vec::new();
+        for (column, &in_key) in item.pk()?.iter().enumerate() {
+            if in_key == 0 {
+                continue;
+            }
+            let value = if insert {
+                item.new_value(column)?
+            } else {
+                item.old_value(column)?
+            };
+            key.push(display_value(&Value::from(value)));
+        }
+        Ok(Conflict {
+            kind,
+            table,
+            key: key.join(", "),
+        })
+    }
+
+    fn on_conflict(conflict: ConflictType, item: &ChangesetItem) -> rusqlite::Result<Conflict> {
+        let kind = match conflict {
+            ConflictType::SQLITE_CHANGESET_DATA => ConflictKind::Changed,
+            ConflictType::SQLITE_CHANGESET_NOTFOUND => ConflictKind::Missing,
+            ConflictType::SQLITE_CHANGESET_CONFLICT => ConflictKind::Exists,
+            ConflictType::SQLITE_CHANGESET_FOREIGN_KEY => {
+                return Ok(Conflict {
+                    kind: ConflictKind::ForeignKey(item.fk_conflicts()?),
+                    table: String::new(),
+                    key: String::new(),
+                });
+            }
+            _ => ConflictKind::Constraint,
+        };
+        describe_conflict(kind, item)
+    }
+
+    /// applies `changeset` to the connection and returns every change that conflicted with the
+    /// current contents of the database; conflicting changes are skipped, the rest are applied
+    pub fn apply(connection: &Connection, mut changeset: &[u8]) -> rusqlite::Result<Vec<Conflict>> {
+        // the conflict handler must be 'static, so it shares the list rather than borrowing it
+        let conflicts = Arc::new(Mutex::new(Vec::new()));
+        let found = Arc::clone(&conflicts);
+        connection.apply_strm(
+            &mut changeset,
+            None::<fn(&str) -> bool>,
+            move |conflict, item| {
+                let conflict = on_conflict(conflict, &item);
+                let mut found = found.lock().unwrap_or_else(|e| e.into_inner());
+                match conflict {
+                    Ok(conflict) => found.push(Ok(conflict)),
+                    Err(e) => {
+                        found.push(Err(e));
+                        return ConflictAction::SQLITE_CHANGESET_ABORT;
+                    }
+                }
+                // skipping the change lets the rest of the changeset report its conflicts too
+                ConflictAction::SQLITE_CHANGESET_OMIT
+            },
+        )?;
+        let conflicts = std::mem::take(&mut *conflicts.lock().unwrap_or_else(|e| e.into_inner()));
+        conflicts.into_iter().collect()
+    }
+}
+
+/// stands in for the session extension when rivet is built without `undo`, so nothing is
+/// recorded and undoing reports why
+#[cfg(not(feature = "undo"))]
+mod unsupported {
+    use super::{Conflict, UNDO_UNSUPPORTED};
+    use color_eyre::eyre::{Result, eyre};
+    use rusqlite::{Connection, ffi};
+
+    /// has no values, since without the session extension nothing can be recorded
+    pub enum Recorder {}
+
+    impl Recorder {
+        /// # Safety
+        ///
+        /// always safe; unsafe only to match the session extension's recorder
+        pub unsafe fn start(_connection: &Connection) -> Result<Self> {
+            Err(eyre!(UNDO_UNSUPPORTED))
+        }
+
+        pub fn changeset(&mut self) -> Result<Vec<u8>> {
+            match *self {}
+        }
+    }
+
+    pub fn invert(_changeset: &[u8]) -> Result<Vec<u8>> {
+        Err(eyre!(UNDO_UNSUPPORTED))
+    }
+
+    pub fn apply(_connection: &Connection, _changeset: &[u8]) -> rusqlite::Result<Vec<Conflict>> {
+        Err(rusqlite::Error::SqliteFailure(
+            ffi::Error::new(ffi::SQLITE_ERROR),
+            Some(UNDO_UNSUPPORTED.to_string()),
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "undo"))]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn orders() -> Connection {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE orders(id INTEGER PRIMARY KEY, total);
+                 INSERT INTO orders VALUES (1, 10), (2, 20);",
+            )
+            .unwrap();
+        connection
+    }
+
+    fn totals(connection: &Connection) -> Vec<(i64, i64)> {
+        let mut statement = connection
+            .prepare("SELECT id, total FROM orders ORDER BY id")
+            .unwrap();
+        statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap()
+    }
+
+    /// records `sql` run on `connection` and returns the changeset
+    fn record(connection: &Connection, sql: &str) -> Vec<u8> {
+        let mut recorder = unsafe { Recorder::start(connection) }.unwrap();
+        connection.execute_batch(sql).unwrap();
+        recorder.changeset().unwrap()
+    }
+
+    #[test]
+    fn applying_the_inverse_undoes_the_changes() {
+        let connection = orders();
+        let changeset = record(
+            &connection,
+            "UPDATE orders SET total = 0 WHERE id = 1; DELETE FROM orders WHERE id = 2;
+             INSERT INTO orders VALUES (3, 30);",
+        );
+        let conflicts = apply(&connection, &invert(&changeset).unwrap()).unwrap();
+        assert!(conflicts.is_empty());
+        assert_eq!(totals(&connection), [(1, 10), (2, 20)]);
+    }
+
+    #[test]
+    fn rows_changed_since_are_reported_as_conflicts() {
+        let connection = orders();
+        let changeset = record(&connection, "UPDATE orders SET total = 0 WHERE id = 1");
+        connection
+            .execute("UPDATE orders SET total = 5 WHERE id = 1", [])
+            .unwrap();
+        let conflicts = apply(&connection, &invert(&changeset).unwrap()).unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].kind, ConflictKind::Changed);
+        assert_eq!(
+            conflicts[0].to_string(),
+            "orders row 1 was changed after the commit"
+        );
+        assert_eq!(totals(&connection), [(1, 5), (2, 20)]);
+    }
+}
@@ -0,0 +1,297 @@
+use crate::model::schema::SchemaCache;
+
+#[rustfmt::skip]
+const KEYWORDS: &[&str] = &[
+    "ABORT", "ADD", "AFTER", "ALL", "ALTER", "ANALYZE", "AND", "AS", "ASC", "ATTACH",
+    "AUTOINCREMENT", "BEFORE", "BEGIN", "BETWEEN", "BY", "CASCADE", "CASE", "CAST", "CHECK",
+    "COLLATE", "COLUMN", "COMMIT", "CONFLICT", "CONSTRAINT", "CREATE", "CROSS", "CURRENT_DATE",
+    "CURRENT_TIME", "CURRENT_TIMESTAMP", "DEFAULT", "DEFERRABLE", "DEFERRED", "DELETE", "DESC",
+    "DETACH", "DISTINCT", "DO", "DROP", "EACH", "ELSE", "END", "ESCAPE", "EXCEPT", "EXCLUSIVE",
+    "EXISTS", "EXPLAIN", "FAIL", "FILTER", "FOREIGN", "FROM", "FULL", "GLOB", "GROUP", "HAVING",
+    "IF", "IGNORE", "IMMEDIATE", "IN", "INDEX", "INDEXED", "INITIALLY", "INNER", "INSERT",
+    "INSTEAD", "INTERSECT", "INTO", "IS", "ISNULL", "JOIN", "KEY", "LEFT", "LIKE", "LIMIT",
+    "MATCH", "MATERIALIZED", "NATURAL", "NOT", "NOTHING", "NOTNULL", "NULL", "NULLS", "OF",
+    "OFFSET", "ON", "OR", "ORDER", "OUTER", "OVER", "PARTITION", "PLAN", "PRAGMA", "PRIMARY",
+    "QUERY", "RAISE", "RECURSIVE", "REFERENCES", "REGEXP", "REINDEX", "RELEASE", "RENAME",
+    "REPLACE", "RESTRICT", "RETURNING", "RIGHT", "ROLLBACK", "ROW", "ROWS", "SAVEPOINT",
+    "SELECT", "SET", "STRICT", "TABLE", "TEMP", "TEMPORARY", "THEN", "TO", "TRANSACTION",
+    "TRIGGER", "UNION", "UNIQUE", "UPDATE", "USING", "VACUUM", "VALUES", "VIEW", "VIRTUAL",
+    "WHEN", "WHERE", "WINDOW", "WITH", "WITHOUT",
+];
+
+#[rustfmt::skip]
+const FUNCTIONS: &[&str] = &[
+    "abs", "avg", "changes", "char", "coalesce", "count", "date", "datetime", "format", "glob",
+    "group_concat", "hex", "ifnull", "iif", "instr", "json", "json_array", "json_extract",
+    "json_group_array", "json_group_object", "json_object", "julianday", "last_insert_rowid",
+    "length", "like", "lower", "ltrim", "max", "min", "nullif", "printf", "quote", "random",
+    "randomblob", "replace", "round", "rtrim", "sign", "soundex", "sqlite_version", "strftime",
+    "string_agg", "substr", "substring", "sum", "time", "total", "total_changes", "trim",
+    "typeof", "unhex", "unicode", "unixepoch", "upper", "zeroblob",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuggestionKind {
+    Column,
+    Table,
+    View,
+    Function,
+    Keyword,
+}
+
+impl SuggestionKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SuggestionKind::Column => "column",
+            SuggestionKind::Table => "table",
+            SuggestionKind::View => "view",
+            SuggestionKind::Function => "function",
+            SuggestionKind::Keyword => "keyword",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub text: String,
+    pub kind: SuggestionKind,
+}
+
+impl Suggestion {
+    /// the text inserted into the input when the suggestion is accepted
+    pub fn insertion(&self) -> String {
+        match self.kind {
+            SuggestionKind::Function => format!("{}(", self.text),
+            _ => self.text.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Completion {
+    /// character index in the input where the completed word starts
+    pub word_start: usize,
+    pub suggestions: Vec<Suggestion>,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// a word, quoted identifier or punctuation character of a statement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// the token's text, without the quotes around a quoted identifier
+    pub text: String,
+    /// quoted identifiers are names even when they spell a keyword, as in `"where"`
+    pub quoted: bool,
+}
+
+impl Token {
+    /// whether the token is the keyword or punctuation `text`, which a quoted identifier never is
+    pub fn is(&self, text: &str) -> bool {
+        !self.quoted && self.text.eq_ignore_ascii_case(text)
+    }
+}
+
+/// splits a statement into identifiers and single punctuation characters,
+/// skipping whitespace, comments and string literals
+pub fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        if c == '\'' {
+            // skip string literals entirely
+            for next in chars.by_ref() {
+                if next == '\'' {
+                    break;
+                }
+            }
+        } else if c == '-' && chars.peek() == Some(&'-') {
+            // a line comment runs to the end of the line
+            for next in chars.by_ref() {
+                if next == '\n' {
+                    break;
+                }
+            }
+        } else if c == '/' && chars.peek() == Some(&'*') {
+            chars.next();
+            let mut previous = None;
+            for next in chars.by_ref() {
+                if previous == Some('*') && next == '/' {
+                    break;
+                }
+                previous = Some(next);
+            }
+        } else if c == '"' || c == '`' || c == '[' {
+            let closing = if c == '[' { ']' } else { c };
+            let mut quoted = String::new();
+            while let Some(next) = chars.next() {
+                if next != closing {
+                    quoted.push(next);
+                } else if closing != ']' && chars.next_if_eq(&closing).is_some() {
+                    // a doubled quote stands for the quote itself
+                    quoted.push(closing);
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token {
+                text: quoted,
+                quoted: true,
+            });
+        } else if is_word_char(c) {
+            let mut word = String::from(c);
+            while let Some(next) = chars.next_if(|next| is_word_char(*next)) {
+                word.push(next);
+            }
+            tokens.push(Token {
+                text: word,
+                quoted: false,
+            });
+        } else {
+            tokens.push(Token {
+                text: c.to_string(),
+                quoted: false,
+            });
+        }
+    }
+
+    tokens
+}
+
+fn is_keyword(token: &str) -> bool {
+    KEYWORDS
+        .iter()
+        .any(|keyword| keyword.eq_ignore_ascii_case(token))
+}
+
+/// returns the (table, alias) pairs named in the FROM and JOIN clauses
+pub fn referenced_tables(input: &str) -> Vec<(String, Option<String>)> {
+    let tokens = tokenize(input);
+    let mut tables: Vec<(String, Option<String>)> = Vec::new();
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let token = &tokens[i];
+        i += 1;
+        if !(token.is("FROM") || token.is("JOIN")) {
+            continue;
+        }
+
+        // a FROM clause may list several tables separated by commas
+        while let Some(table) = tokens.get(i) {
+            if table.is("(") || (!table.quoted && is_keyword(&table.text)) {
+                break;
+            }
+            i += 1;
+
+            if tokens.get(i).is_some_and(|t| t.is("AS")) {
+                i += 1;
+            }
+            let alias = match tokens.get(i) {
+                Some(alias)
+                    if alias.quoted
+                        || (alias.text.chars().all(is_word_char) && !is_keyword(&alias.text)) =>
+                {
+                    i += 1;
+                    Some(alias.text.clone())
+                }
+                _ => None,
+            };
+            tables.push((table.text.clone(), alias));
+
+            if tokens.get(i).is_some_and(|t| t.is(",")) {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    tables
+}
+
+fn push_unique(suggestions: &mut Vec<Suggestion>, text: &str, kind: SuggestionKind) {
+    if !suggestions.iter().any(|s| s.text == text) {
+        suggestions.push(Suggestion {
+            text: text.to_string(),
+            kind,
+        });
+    }
+}
+
+/// builds the completion candidates for the word ending at `cursor`
+pub fn complete(input: &str, cursor: usize, schema: &SchemaCache) -> Completion {
+    let chars: Vec<char> = input.chars().collect();
+    let cursor = cursor.min(chars.len());
+
+    let mut word_start = cursor;
+    while word_start > 0 && is_word_char(chars[word_start - 1]) {
+        word_start -= 1;
+    }
+    let prefix: String = chars[word_start..cursor].iter().collect();
+    let matches = |candidate: &str| candidate.to_lowercase().starts_with(&prefix.to_lowercase());
+
+    let tables = referenced_tables(input);
+    let mut suggestions: Vec<Suggestion> = Vec::new();
+
+    // `alias.` or `table.` only completes that table's columns
+    if word_start > 0 && chars[word_start - 1] == '.' {
+        let mut qualifier_start = word_start - 1;
+        while qualifier_start > 0 && is_word_char(chars[qualifier_start - 1]) {
+            qualifier_start -= 1;
+        }
+        let qualifier: String = chars[qualifier_start..word_start - 1].iter().collect();
+        let table_name = tables
+            .iter()
+            .find(|(_, alias)| {
+                alias
+                    .as_ref()
+                    .is_some_and(|a| a.eq_ignore_ascii_case(&qualifier))
+            })
+            .map(|(table, _)| table.clone())
+            .unwrap_or(qualifier);
+
+        if let Some(object) = schema.find(&table_name) {
+            for column in object.columns.iter().filter(|c| matches(c)) {
+                push_unique(&mut suggestions, column, SuggestionKind::Column);
+            }
+        }
+        return Completion {
+            word_start,
+            suggestions,
+        };
+    }
+
+    for (table, _) in &tables {
+        if let Some(object) = schema.find(table) {
+            for column in object.columns.iter().filter(|c| matches(c)) {
+                push_unique(&mut suggestions, column, SuggestionKind::Column);
+            }
+        }
+    }
+    for object in schema.objects.iter().filter(|o| matches(&o.name)) {
+        let kind = if object.kind == "view" {
+            SuggestionKind::View
+        } else {
+            SuggestionKind::Table
+        };
+        push_unique(&mut suggestions, &object.name, kind);
+    }
+    for function in FUNCTIONS.iter().filter(|f| matches(f)) {
+        push_unique(&mut suggestions, function, SuggestionKind::Function);
+    }
+    for keyword in KEYWORDS.iter().filter(|k| matches(k)) {
+        push_unique(&mut suggestions, keyword, SuggestionKind::Keyword);
+    }
+
+    Completion {
+        word_start,
+        suggestions,
+    }
+}
@@ -0,0 +1,80 @@
+use color_eyre::eyre::Result;
+use rusqlite::Connection;
+use std::fs;
+use std::path::Path;
+
+/// the schema object types counted on the info screen and what they are shown as
+const OBJECT_TYPES: &[(&str, &str)] = &[
+    ("table", "Tables"),
+    ("index", "Indexes"),
+    ("view", "Views"),
+    ("trigger", "Triggers"),
+];
+
+/// what the usual debugging PRAGMAs report about the open database
+#[derive(Debug, Clone)]
+pub struct DatabaseInfo {
+    /// size of the main database file; None for in-memory databases
+    pub file_size: Option<u64>,
+    pub page_size: i64,
+    pub page_count: i64,
+    pub freelist_count: i64,
+    pub journal_mode: String,
+    pub user_version: i64,
+    pub application_id: i64,
+    pub encoding: String,
+    pub auto_vacuum: String,
+    pub sqlite_version: String,
+    pub compile_options: Vec<String>,
+    /// how many user tables, indexes, views and triggers the schema holds, by label
+    pub object_counts: Vec<(&'static str, i64)>,
+}
+
+fn pragma<T: rusqlite::types::FromSql>(connection: &Connection, name: &str) -> Result<T> {
+    Ok(connection.pragma_query_value(None, name, |row| row.get(0))?)
+}
+
+impl DatabaseInfo {
+    pub fn load(connection: &Connection, path: Option<&Path>) -> Result<Self> {
+        let file_size = path
+            .and_then(|path| fs::metadata(path).ok())
+            .map(|metadata| metadata.len());
+        let auto_vacuum = match pragma::<i64>(connection, "auto_vacuum")? {
+            0 => "none".to_string(),
+            1 => "full".to_string(),
+            2 => "incremental".to_string(),
+            other => other.to_string(),
+        };
+
+        let mut statement = connection.prepare("PRAGMA compile_options")?;
+        let compile_options: Vec<String> = statement
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut object_counts: Vec<(&'static str, i64)> = Vec::with_capacity(OBJECT_TYPES.len());
+        for (kind, label) in OBJECT_TYPES {
+            // internal objects such as sqlite_sequence and autoindexes are left out
+            let count = connection.query_row(
+                "SELECT count(*) FROM sqlite_schema WHERE type = ?1 AND name NOT LIKE 'sqlite_%'",
+                [kind],
+                |row| row.get(0),
+            )?;
+            object_counts.push((label, count));
+        }
+
+        Ok(DatabaseInfo {
+            file_size,
+            page_size: pragma(connection, "page_size")?,
+            page_count: pragma(connection, "page_count")?,
+            freelist_count: pragma(connection, "freelist_count")?,
+            journal_mode: pragma(connection, "journal_mode")?,
+            user_version: pragma(connection, "user_version")?,
+            application_id: pragma(connection, "application_id")?,
+            encoding: pragma(connection, "encoding")?,
+            auto_vacuum,
+            sqlite_version: rusqlite::version().to_string(),
+            compile_options,
+            object_counts,
+        })
+    }
+}
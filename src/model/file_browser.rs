@@ -0,0 +1,115 @@
+use color_eyre::eyre::Result;
+use std::env;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{MAIN_SEPARATOR, Path, PathBuf};
+
+/// every SQLite database file starts with these 16 bytes
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    Parent,
+    Directory,
+    Database,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileEntry {
+    pub name: String,
+    pub path: PathBuf,
+    pub kind: EntryKind,
+}
+
+/// whether the file starts with the SQLite header, so other files can be left out of the browser
+pub fn is_sqlite_file(path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .is_ok_and(|()| &header == SQLITE_HEADER)
+}
+
+/// expands a leading `~` to the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), env::var_os("HOME")) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with(MAIN_SEPARATOR) => {
+            PathBuf::from(format!("{}{}", Path::new(&home).display(), rest))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+/// the subdirectories and SQLite databases in `directory`, directories first, hidden entries
+/// left out
+pub fn list_directory(directory: &Path) -> Result<Vec<FileEntry>> {
+    let mut entries: Vec<FileEntry> = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        // metadata follows symlinks, so linked directories can be browsed too
+        let kind = match fs::metadata(&path) {
+            Ok(metadata) if metadata.is_dir() => EntryKind::Directory,
+            Ok(metadata) if metadata.is_file() && is_sqlite_file(&path) => EntryKind::Database,
+            _ => continue,
+        };
+        entries.push(FileEntry { name, path, kind });
+    }
+    entries.sort_by(|a, b| {
+        (a.kind != EntryKind::Directory, &a.name).cmp(&(b.kind != EntryKind::Directory, &b.name))
+    });
+
+    if let Some(parent) = directory.parent() {
+        entries.insert(
+            0,
+            FileEntry {
+                name: "..".to_string(),
+                path: parent.to_path_buf(),
+                kind: EntryKind::Parent,
+            },
+        );
+    }
+    Ok(entries)
+}
+
+fn common_prefix(a: &str, b: &str) -> String {
+    a.chars()
+        .zip(b.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a)
+        .collect()
+}
+
+/// completes the last component of `input` against the directories and databases beside it,
+/// as far as all the matches agree; a lone directory match gets a trailing separator
+pub fn complete_path(input: &str) -> Option<String> {
+    let (directory_part, prefix) = match input.rfind(MAIN_SEPARATOR) {
+        Some(i) => input.split_at(i + 1),
+        None => ("", input),
+    };
+    let directory = if directory_part.is_empty() {
+        PathBuf::from(".")
+    } else {
+        expand_home(directory_part)
+    };
+
+    let matches: Vec<FileEntry> = list_directory(&directory)
+        .ok()?
+        .into_iter()
+        .filter(|entry| entry.kind != EntryKind::Parent && entry.name.starts_with(prefix))
+        .collect();
+    let completion = match matches.as_slice() {
+        [] => return None,
+        [entry] if entry.kind == EntryKind::Directory => {
+            format!("{}{}", entry.name, MAIN_SEPARATOR)
+        }
+        [entry] => entry.name.clone(),
+        [first, rest @ ..] => rest.iter().fold(first.name.clone(), |prefix, entry| {
+            common_prefix(&prefix, &entry.name)
+        }),
+    };
+    Some(format!("{}{}", directory_part, completion))
+}
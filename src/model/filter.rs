@@ -0,0 +1,195 @@
+use crate::model::value::{compare_values, display_value};
+use rusqlite::types::Value;
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Contains,
+}
+
+impl Operator {
+    // longer symbols first so `>=` is not read as `>`
+    const SYMBOLS: [(&'static str, Operator); 8] = [
+        ("!=", Operator::NotEqual),
+        ("<>", Operator::NotEqual),
+        (">=", Operator::GreaterOrEqual),
+        ("<=", Operator::LessOrEqual),
+        ("=", Operator::Equal),
+        (">", Operator::Greater),
+        ("<", Operator::Less),
+        ("~", Operator::Contains),
+    ];
+}
+
+#[derive(Debug, Clone)]
+pub enum RowFilter {
+    /// keeps rows where any cell contains the text, ignoring case
+    Text(String),
+    /// keeps rows where a single column satisfies the comparison
+    Condition {
+        column: usize,
+        operator: Operator,
+        operand: Value,
+        description: String,
+    },
+}
+
+/// numbers and NULL in a condition compare by type, everything else as text
+fn parse_operand(operand: &str) -> Value {
+    let operand = operand.trim().trim_matches('\'');
+    if operand.eq_ignore_ascii_case("NULL") {
+        Value::Null
+    } else if let Ok(i) = operand.parse::<i64>() {
+        Value::Integer(i)
+    } else if let Ok(f) = operand.parse::<f64>() {
+        Value::Real(f)
+    } else {
+        Value::Text(operand.to_string())
+    }
+}
+
+impl RowFilter {
+    /// reads `column <op> value` when the left side names a column, otherwise a text filter
+    pub fn parse(input: &str, columns: &[String]) -> RowFilter {
+        for (symbol, operator) in Operator::SYMBOLS {
+            if let Some((left, right)) = input.split_once(symbol)
+                && let Some(column) = columns
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(left.trim()))
+            {
+                return RowFilter::Condition {
+                    column,
+                    operator,
+                    operand: parse_operand(right),
+                    description: input.trim().to_string(),
+                };
+            }
+        }
+        RowFilter::Text(input.trim().to_string())
+    }
+
+    pub fn matches(&self, cells: &[String], values: &[Value]) -> bool {
+        match self {
+            RowFilter::Text(text) => {
+                let text = text.to_lowercase();
+                cells.iter().any(|cell| cell.to_lowercase().contains(&text))
+            }
+            RowFilter::Condition {
+                column,
+                operator,
+                operand,
+                ..
+            } => {
+                let Some(value) = values.get(*column) else {
+                    return false;
+                };
+                let ordering = compare_values(value, operand);
+                match operator {
+                    Operator::Equal => ordering == Ordering::Equal,
+                    Operator::NotEqual => ordering != Ordering::Equal,
+                    Operator::Less => ordering == Ordering::Less,
+                    Operator::LessOrEqual => ordering != Ordering::Greater,
+                    Operator::Greater => ordering == Ordering::Greater,
+                    Operator::GreaterOrEqual => ordering != Ordering::Less,
+                    Operator::Contains => {
+                        let needle = display_value(operand).to_lowercase();
+                        cells
+                            .get(*column)
+                            .is_some_and(|cell| cell.to_lowercase().contains(&needle))
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        match self {
+            RowFilter::Text(text) => text,
+            RowFilter::Condition { description, .. } => description,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns() -> Vec<String> {
+        vec!["id".to_string(), "name".to_string(), "total".to_string()]
+    }
+
+    fn condition(input: &str) -> (usize, Operator, Value) {
+        match RowFilter::parse(input, &columns()) {
+            RowFilter::Condition {
+                column,
+                operator,
+                operand,
+                ..
+            } => (column, operator, operand),
+            RowFilter::Text(text) => panic!("{:?} was read as the text filter {:?}", input, text),
+        }
+    }
+
+    #[test]
+    fn reads_two_character_operators_whole() {
+        assert_eq!(
+            condition("total >= 100"),
+            (2, Operator::GreaterOrEqual, Value::Integer(100))
+        );
+        assert_eq!(
+            condition("total<=1.5"),
+            (2, Operator::LessOrEqual, Value::Real(1.5))
+        );
+        assert_eq!(
+            condition("id != 3"),
+            (0, Operator::NotEqual, Value::Integer(3))
+        );
+        assert_eq!(
+            condition("id <> 3"),
+            (0, Operator::NotEqual, Value::Integer(3))
+        );
+    }
+
+    #[test]
+    fn column_names_ignore_case_and_operands_keep_their_type() {
+        assert_eq!(
+            condition("NAME = 'Ada'"),
+            (1, Operator::Equal, Value::Text("Ada".to_string()))
+        );
+        assert_eq!(condition("name = null"), (1, Operator::Equal, Value::Null));
+        assert_eq!(
+            condition("name ~ ad"),
+            (1, Operator::Contains, Value::Text("ad".to_string()))
+        );
+    }
+
+    #[test]
+    fn anything_else_is_a_text_filter() {
+        for input in ["  ada  ", "price > 5", "a = b"] {
+            match RowFilter::parse(input, &columns()) {
+                RowFilter::Text(text) => assert_eq!(text, input.trim()),
+                other => panic!("{:?} was read as {:?}", input, other),
+            }
+        }
+    }
+
+    #[test]
+    fn conditions_compare_values_and_text_ignores_case() {
+        let cells = vec!["1".to_string(), "Ada".to_string(), "250".to_string()];
+        let values = vec![
+            Value::Integer(1),
+            Value::Text("Ada".to_string()),
+            Value::Integer(250),
+        ];
+        assert!(RowFilter::parse("total > 100", &columns()).matches(&cells, &values));
+        assert!(!RowFilter::parse("total < 100", &columns()).matches(&cells, &values));
+        assert!(RowFilter::parse("ADA", &columns()).matches(&cells, &values));
+        assert!(!RowFilter::parse("grace", &columns()).matches(&cells, &values));
+    }
+}
@@ -0,0 +1,344 @@
+use clap::ValueEnum;
+
+use crate::model::completion::{Token, tokenize};
+
+/// kinds of statement that can destroy data in bulk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GuardRule {
+    /// DELETE with no WHERE clause
+    UnfilteredDelete,
+    /// UPDATE with no WHERE clause
+    UnfilteredUpdate,
+    DropTable,
+    DropIndex,
+    /// ALTER TABLE ... DROP COLUMN
+    DropColumn,
+    Vacuum,
+}
+
+/// what happens when a statement breaks a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum GuardMode {
+    /// Ask before running the statement
+    #[default]
+    Confirm,
+    /// Refuse to run the statement
+    Block,
+    /// Run everything without checking
+    Off,
+}
+
+#[derive(Debug, Clone)]
+pub struct GuardConfig {
+    pub rules: Vec<GuardRule>,
+    pub mode: GuardMode,
+    /// unfiltered deletes and updates estimated to affect fewer rows than this run without asking
+    pub min_rows: i64,
+}
+
+impl Default for GuardConfig {
+    fn default() -> Self {
+        GuardConfig {
+            rules: GuardRule::value_variants().to_vec(),
+            mode: GuardMode::default(),
+            min_rows: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Risk {
+    pub rule: GuardRule,
+    pub summary: String,
+    /// counts what the statement would remove or overwrite
+    pub count_query: Option<String>,
+    /// what `count_query` counts, as in "rows"
+    pub unit: &'static str,
+}
+
+/// a risky statement together with the estimate of what it affects
+#[derive(Debug, Clone)]
+pub struct GuardWarning {
+    pub risk: Risk,
+    pub affected: Option<i64>,
+}
+
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn is(token: Option<&Token>, keyword: &str) -> bool {
+    token.is_some_and(|token| token.is(keyword))
+}
+
+/// reads a possibly schema-qualified name starting at `i`, returning it quoted with the next index
+fn qualified_name(tokens: &[Token], i: usize) -> Option<(String, usize)> {
+    let first = &tokens.get(i)?.text;
+    if is(tokens.get(i + 1), ".") {
+        let second = &tokens.get(i + 2)?.text;
+        Some((
+            format!("{}.{}", quote_identifier(first), quote_identifier(second)),
+            i + 3,
+        ))
+    } else {
+        Some((quote_identifier(first), i + 1))
+    }
+}
+
+fn skip_if_exists(tokens: &[Token], i: usize) -> usize {
+    if is(tokens.get(i), "IF") && is(tokens.get(i + 1), "EXISTS") {
+        i + 2
+    } else {
+        i
+    }
+}
+
+/// whether WHERE appears outside any parentheses, so subqueries do not count
+fn has_top_level_where(tokens: &[Token]) -> bool {
+    let mut depth = 0usize;
+    for token in tokens {
+        if token.is("(") {
+            depth += 1;
+        } else if token.is(")") {
+            depth = depth.saturating_sub(1);
+        } else if depth == 0 && token.is("WHERE") {
+            return true;
+        }
+    }
+    false
+}
+
+/// keywords that can follow a WITH clause and start the statement it belongs to
+const STATEMENT_KEYWORDS: [&str; 6] = ["DELETE", "UPDATE", "INSERT", "REPLACE", "SELECT", "VALUES"];
+
+/// the statement after a leading WITH clause; the common table expressions sit in parentheses,
+/// so the first statement keyword outside them starts the statement itself
+fn skip_with(tokens: &[Token]) -> &[Token] {
+    if !is(tokens.first(), "WITH") {
+        return tokens;
+    }
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is("(") {
+            depth += 1;
+        } else if token.is(")") {
+            depth = depth.saturating_sub(1);
+        } else if depth == 0 && STATEMENT_KEYWORDS.iter().any(|keyword| token.is(keyword)) {
+            return &tokens[i..];
+        }
+    }
+    &[]
+}
+
+/// whether the statement is a VACUUM, which sqlite refuses to run inside a transaction
+pub fn is_vacuum(query: &str) -> bool {
+    is(tokenize(query).first(), "VACUUM")
+}
+
+fn count_rows(table: &str) -> Option<String> {
+    Some(format!("SELECT count(*) FROM {}", table))
+}
+
+/// recognises statements that match one of the guard rules
+pub fn detect(query: &str) -> Option<Risk> {
+    let tokens = tokenize(query);
+    let tokens = skip_with(&tokens);
+    // a quoted identifier never starts a statement, however it is spelled
+    let first = tokens.first().filter(|token| !token.quoted)?;
+    let first = first.text.to_ascii_uppercase();
+
+    match first.as_str() {
+        "DELETE" if is(tokens.get(1), "FROM") && !has_top_level_where(tokens) => {
+            let (table, _) = qualified_name(tokens, 2)?;
+            Some(Risk {
+                rule: GuardRule::UnfilteredDelete,
+                summary: format!("DELETE without a WHERE clause on {}", table),
+                count_query: count_rows(&table),
+                unit: "rows",
+            })
+        }
+        "UPDATE" if !has_top_level_where(tokens) => {
+            // UPDATE OR REPLACE and friends name the table after the conflict clause
+            let start = if is(tokens.get(1), "OR") { 3 } else { 1 };
+            let (table, _) = qualified_name(tokens, start)?;
+            Some(Risk {
+                rule: GuardRule::UnfilteredUpdate,
+                summary: format!("UPDATE without a WHERE clause on {}", table),
+                count_query: count_rows(&table),
+                unit: "rows",
+            })
+        }
+        "DROP" if is(tokens.get(1), "TABLE") => {
+            let (table, _) = qualified_name(tokens, skip_if_exists(tokens, 2))?;
+            Some(Risk {
+                rule: GuardRule::DropTable,
+                summary: format!("DROP TABLE {}", table),
+                count_query: count_rows(&table),
+                unit: "rows",
+            })
+        }
+        "DROP" if is(tokens.get(1), "INDEX") => {
+            let (index, _) = qualified_name(tokens, skip_if_exists(tokens, 2))?;
+            Some(Risk {
+                rule: GuardRule::DropIndex,
+                summary: format!("DROP INDEX {}", index),
+                count_query: None,
+                unit: "rows",
+            })
+        }
+        "ALTER" if is(tokens.get(1), "TABLE") => {
+            let (table, next) = qualified_name(tokens, 2)?;
+            if !is(tokens.get(next), "DROP") {
+                return None;
+            }
+            let column_index = if is(tokens.get(next + 1), "COLUMN") {
+                next + 2
+            } else {
+                next + 1
+            };
+            let column = quote_identifier(&tokens.get(column_index)?.text);
+            Some(Risk {
+                rule: GuardRule::DropColumn,
+                summary: format!("ALTER TABLE {} DROP COLUMN {}", table, column),
+                count_query: Some(format!("SELECT count({}) FROM {}", column, table)),
+                unit: "non-NULL values",
+            })
+        }
+        "VACUUM" => Some(Risk {
+            rule: GuardRule::Vacuum,
+            summary: "VACUUM rewrites the whole database file".to_string(),
+            count_query: None,
+            unit: "rows",
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(query: &str) -> Option<GuardRule> {
+        detect(query).map(|risk| risk.rule)
+    }
+
+    #[test]
+    fn flags_unfiltered_deletes_and_updates() {
+        assert_eq!(
+            rule("DELETE FROM orders"),
+            Some(GuardRule::UnfilteredDelete)
+        );
+        assert_eq!(
+            rule("update orders set total = 0"),
+            Some(GuardRule::UnfilteredUpdate)
+        );
+        assert_eq!(
+            rule("UPDATE OR REPLACE orders SET total = 0"),
+            Some(GuardRule::UnfilteredUpdate)
+        );
+        assert_eq!(rule("DELETE FROM orders WHERE id = 1"), None);
+        assert_eq!(rule("UPDATE orders SET total = 0 WHERE id = 1"), None);
+    }
+
+    #[test]
+    fn a_where_in_a_comment_does_not_count() {
+        assert_eq!(
+            rule("DELETE FROM orders -- where id = 1"),
+            Some(GuardRule::UnfilteredDelete)
+        );
+        assert_eq!(
+            rule("UPDATE orders SET total = 0 /* WHERE id = 1 */"),
+            Some(GuardRule::UnfilteredUpdate)
+        );
+        assert_eq!(
+            rule("DELETE FROM orders /* all of them */ WHERE id = 1"),
+            None
+        );
+        assert_eq!(
+            rule("-- clean up\nDELETE FROM orders"),
+            Some(GuardRule::UnfilteredDelete)
+        );
+    }
+
+    #[test]
+    fn a_quoted_identifier_is_never_a_keyword() {
+        let risk = detect("DELETE FROM \"where\"").unwrap();
+        assert_eq!(risk.rule, GuardRule::UnfilteredDelete);
+        assert_eq!(
+            risk.count_query.as_deref(),
+            Some("SELECT count(*) FROM \"where\"")
+        );
+        assert_eq!(
+            rule("UPDATE orders SET [where] = 0"),
+            Some(GuardRule::UnfilteredUpdate)
+        );
+        assert_eq!(rule("UPDATE orders SET `where` = 0 WHERE id = 1"), None);
+        assert_eq!(
+            detect("DROP TABLE \"a\"\"b\"")
+                .unwrap()
+                .count_query
+                .as_deref(),
+            Some("SELECT count(*) FROM \"a\"\"b\"")
+        );
+    }
+
+    #[test]
+    fn a_where_in_a_subquery_does_not_count() {
+        assert_eq!(
+            rule("DELETE FROM orders WHERE id IN (SELECT id FROM old)"),
+            None
+        );
+        assert_eq!(
+            rule("UPDATE orders SET total = (SELECT max(total) FROM old WHERE old.id = 1)"),
+            Some(GuardRule::UnfilteredUpdate)
+        );
+    }
+
+    #[test]
+    fn looks_past_a_leading_with_clause() {
+        assert_eq!(
+            rule("WITH old AS (SELECT id FROM orders WHERE total = 0) DELETE FROM orders"),
+            Some(GuardRule::UnfilteredDelete)
+        );
+        assert_eq!(
+            rule(
+                "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 5), m AS (SELECT 2) UPDATE orders SET total = 0"
+            ),
+            Some(GuardRule::UnfilteredUpdate)
+        );
+        assert_eq!(
+            rule("WITH old AS (SELECT 1) DELETE FROM orders WHERE id IN old"),
+            None
+        );
+        assert_eq!(rule("WITH old AS (SELECT 1) SELECT * FROM old"), None);
+    }
+
+    #[test]
+    fn names_the_table_and_counts_its_rows() {
+        let risk = detect("DROP TABLE IF EXISTS main.orders").unwrap();
+        assert_eq!(risk.rule, GuardRule::DropTable);
+        assert_eq!(
+            risk.count_query.as_deref(),
+            Some("SELECT count(*) FROM \"main\".\"orders\"")
+        );
+        assert_eq!(rule("DROP INDEX idx_total"), Some(GuardRule::DropIndex));
+    }
+
+    #[test]
+    fn counts_the_values_a_dropped_column_holds() {
+        let risk = detect("ALTER TABLE orders DROP COLUMN total").unwrap();
+        assert_eq!(risk.rule, GuardRule::DropColumn);
+        assert_eq!(
+            risk.count_query.as_deref(),
+            Some("SELECT count(\"total\") FROM \"orders\"")
+        );
+        assert_eq!(rule("ALTER TABLE orders ADD COLUMN note"), None);
+    }
+
+    #[test]
+    fn recognises_vacuum() {
+        assert_eq!(rule("VACUUM"), Some(GuardRule::Vacuum));
+        assert!(is_vacuum("  vacuum main"));
+        assert!(!is_vacuum("SELECT 'vacuum'"));
+    }
+}
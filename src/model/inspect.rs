@@ -0,0 +1,169 @@
+const HEX_BYTES_PER_LINE: usize = 16;
+const JSON_INDENT: &str = "  ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonToken {
+    Key,
+    String,
+    Number,
+    Literal,
+    Punctuation,
+    Whitespace,
+}
+
+pub type JsonLine = Vec<(JsonToken, String)>;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Lexeme {
+    Punctuation(char),
+    String(String),
+    Number(String),
+    Literal(String),
+}
+
+fn lex_json(text: &str) -> Option<Vec<Lexeme>> {
+    let mut lexemes: Vec<Lexeme> = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '{' | '}' | '[' | ']' | ':' | ',' => lexemes.push(Lexeme::Punctuation(c)),
+            '"' => {
+                let mut string = String::from('"');
+                let mut closed = false;
+                while let Some(next) = chars.next() {
+                    string.push(next);
+                    if next == '\\' {
+                        string.push(chars.next()?);
+                    } else if next == '"' {
+                        closed = true;
+                        break;
+                    }
+                }
+                if !closed {
+                    return None;
+                }
+                lexemes.push(Lexeme::String(string));
+            }
+            '-' | '0'..='9' => {
+                let mut number = String::from(c);
+                while let Some(next) = chars
+                    .next_if(|n| n.is_ascii_digit() || matches!(n, '.' | 'e' | 'E' | '+' | '-'))
+                {
+                    number.push(next);
+                }
+                lexemes.push(Lexeme::Number(number));
+            }
+            c if c.is_ascii_alphabetic() => {
+                let mut word = String::from(c);
+                while let Some(next) = chars.next_if(|n| n.is_ascii_alphabetic()) {
+                    word.push(next);
+                }
+                if !matches!(word.as_str(), "true" | "false" | "null") {
+                    return None;
+                }
+                lexemes.push(Lexeme::Literal(word));
+            }
+            _ => return None,
+        }
+    }
+
+    Some(lexemes)
+}
+
+/// pretty-prints a JSON object or array, returning None for anything else
+pub fn pretty_json(text: &str) -> Option<Vec<JsonLine>> {
+    let lexemes = lex_json(text)?;
+    if !matches!(
+        lexemes.first(),
+        Some(Lexeme::Punctuation('{')) | Some(Lexeme::Punctuation('['))
+    ) {
+        return None;
+    }
+
+    let mut lines: Vec<JsonLine> = Vec::new();
+    let mut line: JsonLine = Vec::new();
+    let mut indent: usize = 0;
+    let mut i = 0;
+
+    let new_line = |lines: &mut Vec<JsonLine>, line: &mut JsonLine, indent: usize| {
+        lines.push(std::mem::take(line));
+        if indent > 0 {
+            line.push((JsonToken::Whitespace, JSON_INDENT.repeat(indent)));
+        }
+    };
+
+    while i < lexemes.len() {
+        match &lexemes[i] {
+            Lexeme::Punctuation(open @ ('{' | '[')) => {
+                line.push((JsonToken::Punctuation, open.to_string()));
+                let close = if *open == '{' { '}' } else { ']' };
+                // keep empty containers on one line
+                if lexemes.get(i + 1) == Some(&Lexeme::Punctuation(close)) {
+                    line.push((JsonToken::Punctuation, close.to_string()));
+                    i += 1;
+                } else {
+                    indent += 1;
+                    new_line(&mut lines, &mut line, indent);
+                }
+            }
+            Lexeme::Punctuation(close @ ('}' | ']')) => {
+                indent = indent.checked_sub(1)?;
+                new_line(&mut lines, &mut line, indent);
+                line.push((JsonToken::Punctuation, close.to_string()));
+            }
+            Lexeme::Punctuation(',') => {
+                line.push((JsonToken::Punctuation, ",".to_string()));
+                new_line(&mut lines, &mut line, indent);
+            }
+            Lexeme::Punctuation(_) => line.push((JsonToken::Punctuation, ": ".to_string())),
+            Lexeme::String(string) => {
+                let token = if lexemes.get(i + 1) == Some(&Lexeme::Punctuation(':')) {
+                    JsonToken::Key
+                } else {
+                    JsonToken::String
+                };
+                line.push((token, string.clone()));
+            }
+            Lexeme::Number(number) => line.push((JsonToken::Number, number.clone())),
+            Lexeme::Literal(literal) => line.push((JsonToken::Literal, literal.clone())),
+        }
+        i += 1;
+    }
+
+    if indent != 0 {
+        return None;
+    }
+    lines.push(line);
+    Some(lines)
+}
+
+/// formats bytes as offset, hex and ASCII columns like `hexdump -C`
+pub fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(HEX_BYTES_PER_LINE)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|byte| format!("{:02x}", byte)).collect();
+            let (first, second) = hex.split_at(hex.len().min(HEX_BYTES_PER_LINE / 2));
+            let ascii: String = chunk
+                .iter()
+                .map(|byte| {
+                    if byte.is_ascii_graphic() || *byte == b' ' {
+                        *byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!(
+                "{:08x}  {:<23}  {:<23}  |{}|",
+                i * HEX_BYTES_PER_LINE,
+                first.join(" "),
+                second.join(" "),
+                ascii
+            )
+        })
+        .collect()
+}
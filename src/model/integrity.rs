@@ -0,0 +1,283 @@
+use color_eyre::eyre::{Result, eyre};
+use rusqlite::{Connection, InterruptHandle};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+    mpsc::{self, Receiver, TryRecvError},
+};
+use std::thread;
+use std::time::Duration;
+
+/// the prefix sqlite puts on integrity messages about the main database
+const MAIN_DATABASE_PREFIX: &str = "*** in database main ***\n";
+/// how many problems integrity_check and quick_check report before they stop
+const MAX_PROBLEMS: u32 = 1000;
+/// how many virtual machine steps pass between calls to the progress handler
+const PROGRESS_STEPS: i32 = 10_000;
+/// how long the check's connection waits for a writer to finish before giving up
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// which PRAGMA a check runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckKind {
+    /// `integrity_check`: everything, including that indexes match their tables
+    Integrity,
+    /// `quick_check`: like `integrity_check` without comparing indexes to their tables
+    Quick,
+    ForeignKeys,
+}
+
+impl CheckKind {
+    pub fn pragma(&self) -> &'static str {
+        match self {
+            CheckKind::Integrity => "integrity_check",
+            CheckKind::Quick => "quick_check",
+            CheckKind::ForeignKeys => "foreign_key_check",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Problem {
+    /// the table the problem is in, or None for problems with the file itself such as
+    /// unused or doubly used pages
+    pub table: Option<String>,
+    /// the row at fault, when sqlite names one
+    pub rowid: Option<i64>,
+    pub message: String,
+}
+
+/// the table each table and index belongs to, for telling which table a message is about
+fn owners(connection: &Connection) -> Result<HashMap<String, String>> {
+    let mut statement = connection
+        .prepare("SELECT name, tbl_name FROM sqlite_schema WHERE type IN ('table', 'index')")?;
+    let owners = statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(owners)
+}
+
+/// the number after "row" or "rowid" in an integrity message, e.g. "row 12 missing from index"
+fn rowid_in(message: &str) -> Option<i64> {
+    let words: Vec<&str> = message.split_whitespace().collect();
+    words.windows(2).find_map(|pair| {
+        let is_row = pair[0].eq_ignore_ascii_case("row") || pair[0].eq_ignore_ascii_case("rowid");
+        is_row
+            .then(|| pair[1].trim_end_matches([',', ':']).parse().ok())
+            .flatten()
+    })
+}
+
+/// the first table or index named in an integrity message, as its table; messages name them
+/// bare, as in "row 3 missing from index idx", or with a column, as in "NULL value in t.a"
+fn table_in(message: &str, owners: &HashMap<String, String>) -> Option<String> {
+    message.split_whitespace().find_map(|word| {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric() && c != '_' && c != '.');
+        owners
+            .get(word)
+            .or_else(|| {
+                let (table, _column) = word.split_once('.')?;
+                owners.get(table)
+            })
+            .cloned()
+    })
+}
+
+/// runs `kind` over the whole database in one pass, returning what it found wrong
+pub fn run(connection: &Connection, kind: CheckKind) -> Result<Vec<Problem>> {
+    match kind {
+        CheckKind::Integrity | CheckKind::Quick => {
+            let owners = owners(connection)?;
+            let mut statement =
+                connection.prepare(&format!("PRAGMA {}({})", kind.pragma(), MAX_PROBLEMS))?;
+            let messages: Vec<String> = statement
+                .query_map([], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?;
+            Ok(messages
+                .into_iter()
+                .filter(|message| message != "ok")
+                .map(|message| {
+                    let message = message
+                        .strip_prefix(MAIN_DATABASE_PREFIX)
+                        .unwrap_or(&message)
+                        .to_string();
+                    Problem {
+                        table: table_in(&message, &owners),
+                        rowid: rowid_in(&message),
+                        message,
+                    }
+                })
+                .collect())
+        }
+        CheckKind::ForeignKeys => {
+            let mut statement = connection.prepare(
+                "SELECT check_result.\"table\", check_result.rowid, check_result.parent,
+                        (SELECT group_concat(\"from\", ', ')
+                         FROM pragma_foreign_key_list(check_result.\"table\")
+                         WHERE id = check_result.fkid),
+                        (SELECT group_concat(\"to\", ', ')
+                         FROM pragma_foreign_key_list(check_result.\"table\")
+                         WHERE id = check_result.fkid)
+                 FROM pragma_foreign_key_check AS check_result",
+            )?;
+            let problems = statement
+                .query_map([], |row| {
+                    let parent: String = row.get(2)?;
+                    let from: Option<String> = row.get(3)?;
+                    // "to" is NULL when the key refers to the parent's primary key
+                    let to: Option<String> = row.get(4)?;
+                    let target = match to.filter(|to| !to.is_empty()) {
+                        Some(to) => format!("{}({})", parent, to),
+                        None => parent,
+                    };
+                    let rowid: Option<i64> = row.get(1)?;
+                    // WITHOUT ROWID tables have no rowid to name the row by
+                    let subject = match rowid {
+                        Some(rowid) => format!("row {}: ", rowid),
+                        None => String::new(),
+                    };
+                    Ok(Problem {
+                        table: row.get(0)?,
+                        rowid,
+                        message: format!(
+                            "{}{} refers to a missing row in {}",
+                            subject,
+                            from.unwrap_or_default(),
+                            target
+                        ),
+                    })
+                })?
+                .collect::<rusqlite::Result<_>>()?;
+            Ok(problems)
+        }
+    }
+}
+
+/// a check running on a thread of its own, so the screen keeps drawing and can cancel it
+pub struct CheckRun {
+    result: Receiver<Result<Vec<Problem>>>,
+    interrupt: InterruptHandle,
+    /// how often the progress handler has been called, which shows the check is moving
+    steps: Arc<AtomicU64>,
+}
+
+impl CheckRun {
+    /// starts `kind` on `connection`, which the check's thread then owns
+    pub fn start(connection: Connection, kind: CheckKind) -> Result<Self> {
+        connection.busy_timeout(BUSY_TIMEOUT)?;
+        let steps = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&steps);
+        connection.progress_handler(
+            PROGRESS_STEPS,
+            Some(move || {
+                counter.fetch_add(1, Ordering::Relaxed);
+                false
+            }),
+        )?;
+        let interrupt = connection.get_interrupt_handle();
+
+        let (sender, result) = mpsc::channel();
+        thread::spawn(move || {
+            // the screen may have gone by the time the check ends
+            let _ = sender.send(run(&connection, kind));
+        });
+        Ok(CheckRun {
+            result,
+            interrupt,
+            steps,
+        })
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps.load(Ordering::Relaxed)
+    }
+
+    /// stops the running statement; the check then ends with an interrupted error
+    pub fn cancel(&self) {
+        self.interrupt.interrupt();
+    }
+
+    /// the check's outcome once it has ended
+    pub fn outcome(&self) -> Option<Result<Vec<Problem>>> {
+        match self.result.try_recv() {
+            Ok(outcome) => Some(outcome),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(eyre!("The check stopped unexpectedly"))),
+        }
+    }
+}
+
+// InterruptHandle has no Debug of its own
+impl fmt::Debug for CheckRun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CheckRun")
+            .field("steps", &self.steps())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owners_of(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, table)| (name.to_string(), table.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn finds_the_rowid_in_a_message() {
+        assert_eq!(rowid_in("row 12 missing from index idx"), Some(12));
+        assert_eq!(rowid_in("rowid 7, missing"), Some(7));
+        assert_eq!(rowid_in("Page 4 is never used"), None);
+    }
+
+    #[test]
+    fn maps_indexes_and_columns_to_their_table() {
+        let owners = owners_of(&[("orders", "orders"), ("idx_customer", "orders")]);
+        assert_eq!(
+            table_in("row 3 missing from index idx_customer", &owners),
+            Some("orders".to_string())
+        );
+        assert_eq!(
+            table_in("NULL value in orders.total", &owners),
+            Some("orders".to_string())
+        );
+        assert_eq!(table_in("Page 9 is never used", &owners), None);
+    }
+
+    #[test]
+    fn reports_foreign_key_violations_by_table() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "PRAGMA foreign_keys = OFF;
+                 CREATE TABLE parent(id INTEGER PRIMARY KEY);
+                 CREATE TABLE child(parent_id REFERENCES parent(id));
+                 INSERT INTO child VALUES (5);",
+            )
+            .unwrap();
+        let problems = run(&connection, CheckKind::ForeignKeys).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].table.as_deref(), Some("child"));
+        assert_eq!(problems[0].rowid, Some(1));
+        assert_eq!(
+            problems[0].message,
+            "row 1: parent_id refers to a missing row in parent(id)"
+        );
+    }
+
+    #[test]
+    fn a_healthy_database_has_no_problems() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch("CREATE TABLE t(a); CREATE INDEX t_a ON t(a); INSERT INTO t VALUES (1);")
+            .unwrap();
+        assert!(run(&connection, CheckKind::Integrity).unwrap().is_empty());
+        assert!(run(&connection, CheckKind::Quick).unwrap().is_empty());
+    }
+}
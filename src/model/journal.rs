@@ -0,0 +1,141 @@
+use color_eyre::eyre::Result;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const JOURNAL_SUFFIX: &str = ".changesets";
+/// each entry starts with the commit time in milliseconds and the changeset's length
+const HEADER_SIZE: usize = 16;
+
+/// a committed transaction as recorded in the journal
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    /// where the entry starts in the journal file
+    pub offset: u64,
+    pub committed_at: SystemTime,
+    pub changeset: Vec<u8>,
+}
+
+/// the sidecar file next to a database that committed changesets are appended to
+#[derive(Debug, Clone)]
+pub struct Journal {
+    pub path: PathBuf,
+}
+
+impl Journal {
+    pub fn for_database(database: &Path) -> Self {
+        let mut path = database.as_os_str().to_owned();
+        path.push(JOURNAL_SUFFIX);
+        Journal { path: path.into() }
+    }
+
+    /// appends a changeset, returning the offset it was written at
+    pub fn append(&self, changeset: &[u8]) -> Result<u64> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let offset = file.metadata()?.len();
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        let mut entry = Vec::with_capacity(HEADER_SIZE + changeset.len());
+        entry.extend_from_slice(&millis.to_le_bytes());
+        entry.extend_from_slice(&(changeset.len() as u64).to_le_bytes());
+        entry.extend_from_slice(changeset);
+        file.write_all(&entry)?;
+        file.sync_data()?;
+        Ok(offset)
+    }
+
+    /// every entry in the journal, oldest first
+    pub fn entries(&self) -> Result<Vec<JournalEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let bytes = fs::read(&self.path)?;
+        let mut entries = Vec::new();
+        let mut offset = 0;
+        // an entry cut short by a crash while appending is ignored
+        while let Some(header) = bytes.get(offset..offset + HEADER_SIZE) {
+            let millis = u64::from_le_bytes(header[..8].try_into()?);
+            let length = u64::from_le_bytes(header[8..].try_into()?) as usize;
+            let start = offset + HEADER_SIZE;
+            let Some(changeset) = bytes.get(start..start + length) else {
+                break;
+            };
+            entries.push(JournalEntry {
+                offset: offset as u64,
+                committed_at: UNIX_EPOCH + Duration::from_millis(millis),
+                changeset: changeset.to_vec(),
+            });
+            offset = start + length;
+        }
+        Ok(entries)
+    }
+
+    /// the most recently committed entry
+    pub fn last(&self) -> Result<Option<JournalEntry>> {
+        Ok(self.entries()?.pop())
+    }
+
+    /// drops the entry at `offset` and everything after it
+    pub fn truncate(&self, offset: u64) -> Result<()> {
+        let file = File::options().write(true).open(&self.path)?;
+        file.set_len(offset)?;
+        file.sync_data()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::scratch_dir::ScratchDir;
+
+    fn scratch_journal(name: &str) -> (ScratchDir, Journal) {
+        let directory = ScratchDir::new(&format!("journal-{}", name));
+        let journal = Journal::for_database(&directory.join("data.db"));
+        (directory, journal)
+    }
+
+    #[test]
+    fn reads_back_what_was_appended_and_truncates_from_an_entry() {
+        let (directory, journal) = scratch_journal("round-trip");
+        assert_eq!(journal.path, directory.join("data.db.changesets"));
+        assert!(journal.entries().unwrap().is_empty());
+
+        let first = journal.append(b"first").unwrap();
+        let second = journal.append(b"").unwrap();
+        let third = journal.append(b"third change").unwrap();
+        assert_eq!(first, 0);
+        assert_eq!(second, (HEADER_SIZE + 5) as u64);
+
+        let entries = journal.entries().unwrap();
+        let changesets: Vec<&[u8]> = entries.iter().map(|e| e.changeset.as_slice()).collect();
+        assert_eq!(changesets, [&b"first"[..], b"", b"third change"]);
+        assert_eq!(journal.last().unwrap().unwrap().offset, third);
+
+        journal.truncate(second).unwrap();
+        let entries = journal.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].changeset, b"first");
+    }
+
+    #[test]
+    fn ignores_an_entry_cut_short() {
+        let (_directory, journal) = scratch_journal("cut-short");
+        journal.append(b"whole").unwrap();
+        let cut = journal.append(b"cut short").unwrap();
+        let file = File::options().write(true).open(&journal.path).unwrap();
+        file.set_len(cut + HEADER_SIZE as u64 + 3).unwrap();
+
+        let entries = journal.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].changeset, b"whole");
+    }
+}
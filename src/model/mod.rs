@@ -1,2 +1,23 @@
+pub mod changeset;
+pub mod completion;
+pub mod database_info;
+pub mod file_browser;
+pub mod filter;
+pub mod guard;
+pub mod inspect;
+pub mod integrity;
+pub mod journal;
 pub mod notifications;
+pub mod open;
+pub mod policy;
+pub mod pragma_settings;
+pub mod query_plan;
+pub mod recent;
+pub mod schema;
+#[cfg(test)]
+pub mod scratch_dir;
+pub mod snapshot;
 pub mod sql_session;
+pub mod statistics;
+pub mod storage;
+pub mod value;
@@ -0,0 +1,71 @@
+use clap::ValueEnum;
+use std::fmt;
+
+/// whether opening a path that does not exist creates a new database there
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum CreateMode {
+    /// Ask before creating the file
+    #[default]
+    Ask,
+    /// Create the file without asking
+    Always,
+    /// Never create a file
+    Never,
+}
+
+/// why a database could not be opened
+#[derive(Debug)]
+pub enum OpenError {
+    /// nothing exists at the path and creating it was not allowed
+    NotFound { path: String },
+    /// the file exists but is not a SQLite database
+    NotADatabase { path: String },
+    /// the file can only be read but a policy that writes was asked for
+    ReadOnlyFile { path: String },
+    /// sqlite failed at one of the steps of opening the database
+    Sqlite {
+        path: String,
+        step: String,
+        source: rusqlite::Error,
+    },
+}
+
+impl OpenError {
+    pub fn sqlite(path: &str, step: impl Into<String>, source: rusqlite::Error) -> Self {
+        OpenError::Sqlite {
+            path: path.to_string(),
+            step: step.into(),
+            source,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, OpenError::NotFound { .. })
+    }
+}
+
+impl fmt::Display for OpenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenError::NotFound { path } => write!(f, "{} does not exist", path),
+            OpenError::NotADatabase { path } => write!(f, "{} is not a SQLite database", path),
+            OpenError::ReadOnlyFile { path } => write!(
+                f,
+                "{} is read-only; open it with --read-only or change its permissions",
+                path
+            ),
+            OpenError::Sqlite { path, step, source } => {
+                write!(f, "Could not {} {}: {}", step, path, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OpenError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OpenError::Sqlite { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
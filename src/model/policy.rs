@@ -0,0 +1,122 @@
+use clap::ValueEnum;
+use rusqlite::hooks::AuthAction;
+
+/// pragmas that take an argument but only report on the database
+const QUERY_PRAGMAS: &[&str] = &[
+    "foreign_key_check",
+    "foreign_key_list",
+    "index_info",
+    "index_list",
+    "index_xinfo",
+    "integrity_check",
+    "quick_check",
+    "table_info",
+    "table_list",
+    "table_xinfo",
+];
+
+const SCHEMA_TABLES: &[&str] = &[
+    "sqlite_master",
+    "sqlite_schema",
+    "sqlite_temp_master",
+    "sqlite_temp_schema",
+];
+
+/// which statements a session may run, enforced by sqlite's authorizer as each one is prepared
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum WritePolicy {
+    /// Queries only; the database is also opened read-only
+    ReadOnly,
+    /// Inserts, updates, deletes and new schema objects, but no DROP, ALTER, ATTACH or PRAGMA writes
+    Data,
+    /// Anything sqlite accepts
+    #[default]
+    Unrestricted,
+}
+
+impl WritePolicy {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WritePolicy::ReadOnly => "read-only",
+            WritePolicy::Data => "data",
+            WritePolicy::Unrestricted => "unrestricted",
+        }
+    }
+
+    /// returns a description of the action when this policy denies it
+    pub fn check(&self, action: &AuthAction) -> Option<String> {
+        let (needs_unrestricted, description) = match *action {
+            // sqlite touches its own schema table while preparing some statements; editing it
+            // directly needs PRAGMA writable_schema, which is checked like any other pragma
+            AuthAction::Update { table_name, .. } | AuthAction::Insert { table_name }
+                if SCHEMA_TABLES.contains(&table_name) =>
+            {
+                return None;
+            }
+            AuthAction::Insert { table_name } => (false, format!("INSERT INTO {}", table_name)),
+            AuthAction::Update { table_name, .. } => (false, format!("UPDATE {}", table_name)),
+            AuthAction::Delete { table_name } => (false, format!("DELETE FROM {}", table_name)),
+            AuthAction::CreateTable { table_name } => {
+                (false, format!("CREATE TABLE {}", table_name))
+            }
+            AuthAction::CreateIndex { index_name, .. } => {
+                (false, format!("CREATE INDEX {}", index_name))
+            }
+            AuthAction::CreateView { view_name } => (false, format!("CREATE VIEW {}", view_name)),
+            AuthAction::CreateTrigger { trigger_name, .. } => {
+                (false, format!("CREATE TRIGGER {}", trigger_name))
+            }
+            AuthAction::CreateVtable { table_name, .. } => {
+                (false, format!("CREATE VIRTUAL TABLE {}", table_name))
+            }
+            AuthAction::Reindex { index_name } => (false, format!("REINDEX {}", index_name)),
+            AuthAction::Analyze { table_name } => (false, format!("ANALYZE {}", table_name)),
+            AuthAction::DropTable { table_name } => (true, format!("DROP TABLE {}", table_name)),
+            AuthAction::DropIndex { index_name, .. } => {
+                (true, format!("DROP INDEX {}", index_name))
+            }
+            AuthAction::DropView { view_name } => (true, format!("DROP VIEW {}", view_name)),
+            AuthAction::DropTrigger { trigger_name, .. } => {
+                (true, format!("DROP TRIGGER {}", trigger_name))
+            }
+            AuthAction::DropVtable { table_name, .. } => {
+                (true, format!("DROP TABLE {}", table_name))
+            }
+            AuthAction::AlterTable { table_name, .. } => {
+                (true, format!("ALTER TABLE {}", table_name))
+            }
+            AuthAction::Attach { filename } => (true, format!("ATTACH '{}'", filename)),
+            AuthAction::Detach { database_name } => (true, format!("DETACH {}", database_name)),
+            AuthAction::Pragma {
+                pragma_name,
+                pragma_value: Some(value),
+            } if !QUERY_PRAGMAS.contains(&pragma_name.to_ascii_lowercase().as_str()) => {
+                (true, format!("PRAGMA {} = {}", pragma_name, value))
+            }
+            // reads, transactions and temporary objects change nothing on disk
+            AuthAction::Read { .. }
+            | AuthAction::Select
+            | AuthAction::Function { .. }
+            | AuthAction::Recursive
+            | AuthAction::Pragma { .. }
+            | AuthAction::Transaction { .. }
+            | AuthAction::Savepoint { .. }
+            | AuthAction::CreateTempTable { .. }
+            | AuthAction::CreateTempIndex { .. }
+            | AuthAction::CreateTempView { .. }
+            | AuthAction::CreateTempTrigger { .. }
+            | AuthAction::DropTempTable { .. }
+            | AuthAction::DropTempIndex { .. }
+            | AuthAction::DropTempView { .. }
+            | AuthAction::DropTempTrigger { .. } => return None,
+            _ => (true, "an unrecognised operation".to_string()),
+        };
+
+        let allowed = match self {
+            WritePolicy::ReadOnly => false,
+            WritePolicy::Data => !needs_unrestricted,
+            WritePolicy::Unrestricted => true,
+        };
+        (!allowed).then_some(description)
+    }
+}
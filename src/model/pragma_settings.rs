@@ -0,0 +1,214 @@
+use color_eyre::eyre::{Result, eyre};
+use rusqlite::{Connection, types::Value};
+
+/// where a pragma's value lives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PragmaScope {
+    /// lasts until the connection closes
+    Connection,
+    /// written into the database file, so every later connection sees it
+    File,
+}
+
+impl PragmaScope {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PragmaScope::Connection => "connection",
+            PragmaScope::File => "file",
+        }
+    }
+}
+
+/// what a pragma accepts
+#[derive(Debug, Clone, Copy)]
+pub enum PragmaKind {
+    Boolean,
+    /// named values; sqlite reports numeric ones as the index into the list
+    Choice(&'static [&'static str]),
+    Integer {
+        min: i64,
+        max: i64,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PragmaSetting {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub scope: PragmaScope,
+    pub kind: PragmaKind,
+}
+
+const fn setting(
+    name: &'static str,
+    scope: PragmaScope,
+    kind: PragmaKind,
+    description: &'static str,
+) -> PragmaSetting {
+    PragmaSetting {
+        name,
+        description,
+        scope,
+        kind,
+    }
+}
+
+/// the pragmas the settings screen lists, in the order it lists them
+pub const PRAGMA_SETTINGS: &[PragmaSetting] = &[
+    setting(
+        "foreign_keys",
+        PragmaScope::Connection,
+        PragmaKind::Boolean,
+        "Enforce foreign key constraints",
+    ),
+    setting(
+        "journal_mode",
+        PragmaScope::File,
+        PragmaKind::Choice(&["delete", "truncate", "persist", "memory", "wal", "off"]),
+        "How transactions are journaled; only wal is kept in the file",
+    ),
+    setting(
+        "synchronous",
+        PragmaScope::Connection,
+        PragmaKind::Choice(&["off", "normal", "full", "extra"]),
+        "How often sqlite waits for writes to reach the disk",
+    ),
+    setting(
+        "busy_timeout",
+        PragmaScope::Connection,
+        PragmaKind::Integer {
+            min: 0,
+            max: i32::MAX as i64,
+        },
+        "Milliseconds to wait for a lock before giving up",
+    ),
+    setting(
+        "cache_size",
+        PragmaScope::Connection,
+        PragmaKind::Integer {
+            min: i64::MIN,
+            max: i64::MAX,
+        },
+        "Page cache size in pages, or in KiB when negative",
+    ),
+    setting(
+        "temp_store",
+        PragmaScope::Connection,
+        PragmaKind::Choice(&["default", "file", "memory"]),
+        "Where temporary tables and indexes are kept",
+    ),
+    setting(
+        "mmap_size",
+        PragmaScope::Connection,
+        PragmaKind::Integer {
+            min: 0,
+            max: i64::MAX,
+        },
+        "Bytes of the file to read through memory mapping",
+    ),
+    setting(
+        "recursive_triggers",
+        PragmaScope::Connection,
+        PragmaKind::Boolean,
+        "Let triggers fire other triggers recursively",
+    ),
+    setting(
+        "automatic_index",
+        PragmaScope::Connection,
+        PragmaKind::Boolean,
+        "Build temporary indexes for queries that lack one",
+    ),
+    setting(
+        "secure_delete",
+        PragmaScope::Connection,
+        PragmaKind::Boolean,
+        "Overwrite deleted content with zeros",
+    ),
+    setting(
+        "user_version",
+        PragmaScope::File,
+        PragmaKind::Integer {
+            min: i32::MIN as i64,
+            max: i32::MAX as i64,
+        },
+        "Schema version number kept for the application",
+    ),
+    setting(
+        "application_id",
+        PragmaScope::File,
+        PragmaKind::Integer {
+            min: i32::MIN as i64,
+            max: i32::MAX as i64,
+        },
+        "Identifies the application that owns the file",
+    ),
+    setting(
+        "auto_vacuum",
+        PragmaScope::File,
+        PragmaKind::Choice(&["none", "full", "incremental"]),
+        "Give free pages back to the file system; needs VACUUM to switch on",
+    ),
+    setting(
+        "page_size",
+        PragmaScope::File,
+        PragmaKind::Integer {
+            min: 512,
+            max: 65536,
+        },
+        "Bytes per page; takes effect on an empty database or after VACUUM",
+    ),
+];
+
+impl PragmaSetting {
+    /// the current value, with numeric choices shown by name
+    pub fn read(&self, connection: &Connection) -> Result<String> {
+        let value: Value = connection.pragma_query_value(None, self.name, |row| row.get(0))?;
+        Ok(match (self.kind, value) {
+            (PragmaKind::Boolean, Value::Integer(0)) => "off".to_string(),
+            (PragmaKind::Boolean, Value::Integer(_)) => "on".to_string(),
+            (PragmaKind::Choice(choices), Value::Integer(i)) => usize::try_from(i)
+                .ok()
+                .and_then(|i| choices.get(i))
+                .map(|choice| choice.to_string())
+                .unwrap_or_else(|| i.to_string()),
+            (_, Value::Integer(i)) => i.to_string(),
+            (_, Value::Text(text)) => text.to_lowercase(),
+            (_, value) => format!("{:?}", value),
+        })
+    }
+
+    /// checks typed input against what the pragma accepts, returning it as a literal that is
+    /// safe to put into the PRAGMA statement
+    pub fn validate(&self, input: &str) -> Result<String> {
+        let input = input.trim().to_lowercase();
+        match self.kind {
+            PragmaKind::Boolean => match input.as_str() {
+                "on" | "true" | "yes" | "1" => Ok("ON".to_string()),
+                "off" | "false" | "no" | "0" => Ok("OFF".to_string()),
+                _ => Err(eyre!("{} takes on or off", self.name)),
+            },
+            PragmaKind::Choice(choices) => choices
+                .iter()
+                .find(|choice| **choice == input)
+                .map(|choice| choice.to_uppercase())
+                .ok_or_else(|| eyre!("{} takes one of {}", self.name, choices.join(", "))),
+            PragmaKind::Integer { min, max } => {
+                let value: i64 = input
+                    .parse()
+                    .map_err(|_| eyre!("{} takes a whole number", self.name))?;
+                if !(min..=max).contains(&value) {
+                    return Err(eyre!(
+                        "{} takes a number from {} to {}",
+                        self.name,
+                        min,
+                        max
+                    ));
+                }
+                if self.name == "page_size" && !(value as u64).is_power_of_two() {
+                    return Err(eyre!("page_size must be a power of two"));
+                }
+                Ok(value.to_string())
+            }
+        }
+    }
+}
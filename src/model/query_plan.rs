@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct PlanRow {
+    pub id: i64,
+    pub parent: i64,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlanLine {
+    /// tree connectors drawn before the detail text
+    pub prefix: String,
+    pub detail: String,
+    pub warning: bool,
+}
+
+/// full table scans, temporary b-trees and automatic indexes are worth a second look
+fn is_warning(detail: &str) -> bool {
+    let detail = detail.to_ascii_uppercase();
+    (detail.starts_with("SCAN ") && !detail.contains(" USING "))
+        || detail.contains("TEMP B-TREE")
+        || detail.contains("AUTOMATIC")
+}
+
+/// turns the id/parent rows of EXPLAIN QUERY PLAN into an indented tree
+pub fn build_tree(rows: &[PlanRow]) -> Vec<PlanLine> {
+    let mut child_prefixes: HashMap<i64, String> = HashMap::new();
+    child_prefixes.insert(0, String::new());
+    let mut lines: Vec<PlanLine> = Vec::with_capacity(rows.len());
+
+    for (i, row) in rows.iter().enumerate() {
+        let is_last = !rows[i + 1..].iter().any(|later| later.parent == row.parent);
+        let parent_prefix = child_prefixes.get(&row.parent).cloned().unwrap_or_default();
+        let connector = if is_last { "└─ " } else { "├─ " };
+        let continuation = if is_last { "   " } else { "│  " };
+
+        child_prefixes.insert(row.id, format!("{}{}", parent_prefix, continuation));
+        lines.push(PlanLine {
+            prefix: format!("{}{}", parent_prefix, connector),
+            detail: row.detail.clone(),
+            warning: is_warning(&row.detail),
+        });
+    }
+
+    lines
+}
@@ -0,0 +1,64 @@
+use color_eyre::eyre::Result;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// how many databases the recent list remembers
+const MAX_RECENT_FILES: usize = 20;
+
+/// databases opened in earlier runs, most recent first, kept in
+/// `$XDG_STATE_HOME/rivet/recent` (or `~/.local/state/rivet/recent`)
+#[derive(Debug, Clone, Default)]
+pub struct RecentFiles {
+    /// where the list is saved; None when there is no home directory to keep it in
+    file: Option<PathBuf>,
+    pub paths: Vec<String>,
+}
+
+fn state_file() -> Option<PathBuf> {
+    let state_home = env::var_os("XDG_STATE_HOME")
+        .filter(|state_home| !state_home.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/state")))?;
+    Some(state_home.join("rivet").join("recent"))
+}
+
+impl RecentFiles {
+    /// reads the saved list; a missing or unreadable file gives an empty one
+    pub fn load() -> Self {
+        let file = state_file();
+        let paths = file
+            .as_ref()
+            .and_then(|file| fs::read_to_string(file).ok())
+            .map(|contents| {
+                contents
+                    .lines()
+                    .filter(|line| !line.is_empty())
+                    .map(|line| line.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        RecentFiles { file, paths }
+    }
+
+    /// moves `path` to the top of the list and saves it
+    pub fn add(&mut self, path: &Path) -> Result<()> {
+        let path = path
+            .canonicalize()
+            .unwrap_or_else(|_| path.to_path_buf())
+            .display()
+            .to_string();
+        self.paths.retain(|recent| *recent != path);
+        self.paths.insert(0, path);
+        self.paths.truncate(MAX_RECENT_FILES);
+
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        if let Some(directory) = file.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(file, self.paths.join("\n") + "\n")?;
+        Ok(())
+    }
+}
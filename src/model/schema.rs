@@ -0,0 +1,71 @@
+use color_eyre::eyre::Result;
+use rusqlite::Connection;
+
+#[derive(Debug, Clone)]
+pub struct SchemaObject {
+    pub name: String,
+    pub kind: String,
+    pub columns: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct SchemaCache {
+    pub objects: Vec<SchemaObject>,
+    schema_version: i64,
+}
+
+impl SchemaCache {
+    pub fn load(connection: &Connection) -> Result<Self> {
+        let mut cache = SchemaCache::default();
+        cache.refresh(connection)?;
+        Ok(cache)
+    }
+
+    /// reloads tables, views and their columns from sqlite_schema
+    pub fn refresh(&mut self, connection: &Connection) -> Result<()> {
+        let mut statement = connection.prepare(
+            "SELECT name, type FROM sqlite_schema
+             WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite_%'
+             ORDER BY name",
+        )?;
+        let names: Vec<(String, String)> = statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut objects: Vec<SchemaObject> = Vec::with_capacity(names.len());
+        for (name, kind) in names {
+            let mut columns_statement =
+                connection.prepare("SELECT name FROM pragma_table_info(?1)")?;
+            let columns: Vec<String> = columns_statement
+                .query_map([&name], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?;
+            objects.push(SchemaObject {
+                name,
+                kind,
+                columns,
+            });
+        }
+
+        self.objects = objects;
+        self.schema_version = Self::schema_version(connection)?;
+        Ok(())
+    }
+
+    /// refreshes the cache only if the schema changed since it was last loaded
+    pub fn refresh_if_changed(&mut self, connection: &Connection) -> Result<()> {
+        if Self::schema_version(connection)? != self.schema_version {
+            self.refresh(connection)?;
+        }
+        Ok(())
+    }
+
+    pub fn find(&self, name: &str) -> Option<&SchemaObject> {
+        self.objects
+            .iter()
+            .find(|object| object.name.eq_ignore_ascii_case(name))
+    }
+
+    fn schema_version(connection: &Connection) -> Result<i64> {
+        Ok(connection.query_row("PRAGMA schema_version", [], |row| row.get(0))?)
+    }
+}
@@ -0,0 +1,153 @@
+use color_eyre::eyre::Result;
+use rusqlite::{Connection, MAIN_DB};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SNAPSHOT_EXTENSION: &str = "snapshot";
+
+#[derive(Debug, Clone)]
+pub struct SnapshotConfig {
+    pub directory: PathBuf,
+    /// how many snapshots of each database are kept before the oldest are deleted
+    pub keep: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub path: PathBuf,
+    pub taken_at: SystemTime,
+    pub size: u64,
+}
+
+/// FNV-1a, which unlike std's hasher is guaranteed to give the same hash in every build
+fn path_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// snapshot files are named after the database they were taken from, with a hash of its
+/// canonical path so databases of the same name in different directories are kept apart
+fn file_prefix(database: &Path) -> String {
+    let stem = database
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "database".to_string());
+    let canonical = database
+        .canonicalize()
+        .unwrap_or_else(|_| database.to_path_buf());
+    format!(
+        "{}-{:016x}.",
+        stem,
+        path_hash(canonical.as_os_str().as_encoded_bytes())
+    )
+}
+
+impl SnapshotConfig {
+    /// copies `database` through `connection`, its own connection, into the snapshot directory;
+    /// taken outside any transaction it holds exactly what is committed, and it waits for other
+    /// connections' locks as long as the connection's busy timeout allows
+    pub fn take(&self, connection: &Connection, database: &Path) -> Result<PathBuf> {
+        fs::create_dir_all(&self.directory)?;
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let path = self.directory.join(format!(
+            "{}{}.{}",
+            file_prefix(database),
+            millis,
+            SNAPSHOT_EXTENSION
+        ));
+
+        connection.backup(MAIN_DB, &path, None)?;
+        Ok(path)
+    }
+
+    /// snapshots of `database`, newest first
+    pub fn list(&self, database: &Path) -> Result<Vec<Snapshot>> {
+        if !self.directory.exists() {
+            return Ok(Vec::new());
+        }
+
+        let prefix = file_prefix(database);
+        let mut snapshots: Vec<Snapshot> = Vec::new();
+        for entry in fs::read_dir(&self.directory)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(millis) = name
+                .strip_prefix(&prefix)
+                .and_then(|rest| rest.strip_suffix(&format!(".{}", SNAPSHOT_EXTENSION)))
+                .and_then(|millis| millis.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            snapshots.push(Snapshot {
+                path: entry.path(),
+                taken_at: UNIX_EPOCH + Duration::from_millis(millis),
+                size: entry.metadata()?.len(),
+            });
+        }
+
+        snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.taken_at));
+        Ok(snapshots)
+    }
+
+    /// deletes all but the newest `keep` snapshots of `database`
+    pub fn prune(&self, database: &Path) -> Result<()> {
+        for snapshot in self.list(database)?.iter().skip(self.keep.max(1)) {
+            fs::remove_file(&snapshot.path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::scratch_dir::ScratchDir;
+
+    fn create_database(path: &Path) -> Connection {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let connection = Connection::open(path).unwrap();
+        connection
+            .execute_batch("CREATE TABLE t(a); INSERT INTO t VALUES (1);")
+            .unwrap();
+        connection
+    }
+
+    #[test]
+    fn databases_with_the_same_name_keep_separate_snapshots() {
+        let root = ScratchDir::new("snapshot-names");
+        let first = root.join("a").join("app.db");
+        let second = root.join("b").join("app.db");
+        let first_connection = create_database(&first);
+        let second_connection = create_database(&second);
+        let config = SnapshotConfig {
+            directory: root.join("snapshots"),
+            keep: 1,
+        };
+
+        config.take(&first_connection, &first).unwrap();
+        config.take(&second_connection, &second).unwrap();
+        // snapshots are named by the millisecond, so the next one needs a later name
+        std::thread::sleep(Duration::from_millis(5));
+        config.take(&second_connection, &second).unwrap();
+        config.prune(&second).unwrap();
+
+        assert_eq!(config.list(&first).unwrap().len(), 1);
+        assert_eq!(config.list(&second).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn the_same_file_reached_by_two_paths_shares_its_snapshots() {
+        let root = ScratchDir::new("snapshot-paths");
+        let database = root.join("app.db");
+        create_database(&database);
+        assert_eq!(
+            file_prefix(&database),
+            file_prefix(&root.join(".").join("app.db"))
+        );
+    }
+}
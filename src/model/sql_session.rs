@@ -1,10 +1,69 @@
-use color_eyre::eyre::{Result, eyre};
-use rusqlite::{Connection, types::ValueRef};
+use crate::model::changeset::{self, Conflict, Recorder};
+use crate::model::database_info::DatabaseInfo;
+use crate::model::file_browser::is_sqlite_file;
+use crate::model::guard::{self, GuardConfig, GuardMode, GuardRule, GuardWarning};
+use crate::model::integrity::{CheckKind, CheckRun};
+use crate::model::journal::{Journal, JournalEntry};
+use crate::model::open::OpenError;
+use crate::model::policy::WritePolicy;
+use crate::model::pragma_settings::PragmaSetting;
+use crate::model::query_plan::PlanRow;
+use crate::model::schema::SchemaCache;
+use crate::model::snapshot::{Snapshot, SnapshotConfig};
+use crate::model::statistics::QueryStats;
+use crate::model::storage::StorageReport;
+use color_eyre::eyre::{Report, Result, eyre};
+use rusqlite::backup::{Backup, Progress};
+use rusqlite::hooks::{AuthContext, Authorization};
+use rusqlite::{Connection, ErrorCode, MAIN_DB, OpenFlags, types::Value};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// settings every database opened during a run gets
+#[derive(Debug, Clone, Default)]
+pub struct SessionSettings {
+    pub guard: GuardConfig,
+    pub snapshots: Option<SnapshotConfig>,
+    /// whether commits are journaled so they can be undone
+    pub journal: bool,
+}
 
 pub struct SqlSession {
+    /// records the open transaction for the journal; declared before `connection` so it is
+    /// dropped before the connection closes
+    recorder: Option<Recorder>,
     connection: Connection,
     transaction_active: bool,
+    /// statements run since the transaction began, counting ones that failed since the
+    /// transaction stays open after them
+    pending_statements: usize,
     pub read_only: bool,
+    pub policy: WritePolicy,
+    pub guard: GuardConfig,
+    /// where copies of the database are kept before each commit, when enabled
+    pub snapshots: Option<SnapshotConfig>,
+    /// where committed changesets are kept so commits can be undone, when enabled
+    pub journal: Option<Journal>,
+    /// the journal entry the open transaction undoes, dropped from the journal on commit
+    undoing: Option<u64>,
+    /// the snapshot taken as the open transaction began, deleted again if it is rolled back
+    snapshot: Option<PathBuf>,
+    /// the file an in-memory scratch database was copied from
+    pub scratch_source: Option<String>,
+    /// whether an in-memory database had changes committed since it was created or last saved
+    unsaved_changes: bool,
+    /// the action the authorizer last refused, used to explain the resulting error
+    denied: Arc<Mutex<Option<String>>>,
+    pub schema: SchemaCache,
+    pub last_stats: Option<QueryStats>,
+}
+
+/// deletes the snapshot of a transaction that was rolled back, since nothing was committed
+fn discard_snapshot(snapshot: Option<PathBuf>) {
+    if let Some(snapshot) = snapshot {
+        let _ = std::fs::remove_file(snapshot);
+    }
 }
 
 impl SqlSession {
@@ -23,46 +82,183 @@ impl SqlSession {
         Ok(column_names)
     }
 
-    pub fn new(sql_path: String, read_only: bool) -> Self {
-        // attempt to connect to database
-        let connection = match Connection::open(&sql_path) {
+    /// opens the database at `sql_path`, creating an empty one when it does not exist only if
+    /// `create` is set
+    pub fn new(sql_path: String, policy: WritePolicy, create: bool) -> Result<Self, OpenError> {
+        let read_only = policy == WritePolicy::ReadOnly;
+        let flags = if read_only {
+            OpenFlags::SQLITE_OPEN_READ_ONLY
+                | OpenFlags::SQLITE_OPEN_URI
+                | OpenFlags::SQLITE_OPEN_NO_MUTEX
+        } else if create {
+            OpenFlags::default()
+        } else {
+            OpenFlags::default() & !OpenFlags::SQLITE_OPEN_CREATE
+        };
+
+        let connection = match Connection::open_with_flags(&sql_path, flags) {
             Ok(connection) => connection,
-            Err(e) => {
-                eprintln!("SqlSession error: {}", e);
-                std::process::exit(1);
+            Err(e)
+                if (!create || read_only)
+                    && e.sqlite_error_code() == Some(ErrorCode::CannotOpen)
+                    && !Path::new(&sql_path).exists() =>
+            {
+                return Err(OpenError::NotFound { path: sql_path });
             }
+            Err(e) => return Err(OpenError::sqlite(&sql_path, "open", e)),
         };
-        //check to see if the database is read_only
-        let read_only_db: bool = match connection.is_readonly("main") {
-            Ok(read_only_db) => read_only_db,
-            Err(e) => {
-                eprintln!("Failed to determine if {} is read_only", e);
-                std::process::exit(1)
+        let read_only_db = connection
+            .is_readonly(MAIN_DB)
+            .map_err(|e| OpenError::sqlite(&sql_path, "check the permissions of", e))?;
+        // a file sqlite can only read cannot take the policy's writes
+        if read_only_db && !read_only {
+            return Err(OpenError::ReadOnlyFile { path: sql_path });
+        }
+
+        // sqlite reads the header lazily, so a file that is not a database only fails here
+        if let Err(e) = connection.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(())) {
+            return Err(match e.sqlite_error_code() {
+                Some(ErrorCode::NotADatabase) => OpenError::NotADatabase { path: sql_path },
+                _ => OpenError::sqlite(&sql_path, "read", e),
+            });
+        }
+
+        if read_only {
+            connection
+                .pragma_update(None, "query_only", true)
+                .map_err(|e| OpenError::sqlite(&sql_path, "make query only", e))?;
+        }
+
+        let denied = Arc::new(Mutex::new(None));
+        let authorizer_denied = Arc::clone(&denied);
+        let authorizer = move |context: AuthContext<'_>| match policy.check(&context.action) {
+            Some(description) => {
+                if let Ok(mut denied) = authorizer_denied.lock() {
+                    *denied = Some(description);
+                }
+                Authorization::Deny
             }
+            None => Authorization::Allow,
         };
-        // if it is a read only database and user specifies write operations throw an error
-        if read_only_db & !read_only {
-            eprint!(
-                "Unable to open {0} in write mode. {0} is a read only database.",
-                sql_path
-            );
-            std::process::exit(1);
-        }
+        connection.authorizer(Some(authorizer)).map_err(|e| {
+            OpenError::sqlite(
+                &sql_path,
+                format!("install the {} policy on", policy.name()),
+                e,
+            )
+        })?;
+
+        // a schema that fails to load only disables autocompletion
+        let schema = SchemaCache::load(&connection).unwrap_or_default();
 
-        SqlSession {
+        Ok(SqlSession {
+            recorder: None,
             connection,
             transaction_active: false,
+            pending_statements: 0,
             read_only,
+            policy,
+            guard: GuardConfig::default(),
+            snapshots: None,
+            journal: None,
+            undoing: None,
+            snapshot: None,
+            scratch_source: None,
+            unsaved_changes: false,
+            denied,
+            schema,
+            last_stats: None,
+        })
+    }
+
+    /// an in-memory database, empty or holding a copy of `source`, that is never written back
+    pub fn scratch(source: Option<String>, policy: WritePolicy) -> Result<Self, OpenError> {
+        let mut session = SqlSession::new(":memory:".to_string(), policy, true)?;
+        let Some(source) = source else {
+            return Ok(session);
+        };
+        if !Path::new(&source).exists() {
+            return Err(OpenError::NotFound { path: source });
+        }
+        if !is_sqlite_file(Path::new(&source)) {
+            return Err(OpenError::NotADatabase { path: source });
+        }
+        session
+            .connection
+            .restore(MAIN_DB, &source, None::<fn(Progress)>)
+            .map_err(|e| OpenError::sqlite(&source, "copy", e))?;
+        session.schema = SchemaCache::load(&session.connection).unwrap_or_default();
+        session.scratch_source = Some(source);
+        Ok(session)
+    }
+
+    /// whether the database lives only in memory and is lost when the session ends
+    pub fn is_in_memory(&self) -> bool {
+        self.database_path().is_err()
+    }
+
+    /// whether ending the session loses changes that were never written to a file: an
+    /// in-memory database with changes committed or staged since it was created or last saved
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.unsaved_changes || (self.transaction_active && self.is_in_memory())
+    }
+
+    /// writes a copy of the committed database to a new file with `VACUUM INTO`
+    pub fn save_as(&mut self, path: &str) -> Result<()> {
+        if self.transaction_active {
+            return Err(eyre!(
+                "Commit or roll back the open transaction before saving a copy"
+            ));
         }
+        if Path::new(path).exists() {
+            return Err(eyre!("{} already exists", path));
+        }
+        // sqlite authorizes VACUUM INTO as attaching the new file, which only the unrestricted
+        // policy allows
+        self.take_denied();
+        self.connection
+            .execute("VACUUM INTO ?1", [path])
+            .map_err(|e| match self.take_denied() {
+                Some(_) => eyre!(
+                    "Saving a copy is not allowed by the {} policy",
+                    self.policy.name()
+                ),
+                None => e.into(),
+            })?;
+        self.unsaved_changes = false;
+        Ok(())
     }
 
-    pub fn select(&self, query: &str) -> Result<Vec<Vec<String>>> {
+    fn denial_pending(&self) -> bool {
+        self.denied.lock().is_ok_and(|denied| denied.is_some())
+    }
+
+    fn take_denied(&self) -> Option<String> {
+        self.denied.lock().ok().and_then(|mut denied| denied.take())
+    }
+
+    /// explains errors caused by the authorizer in terms of the policy
+    fn policy_error(&self, error: rusqlite::Error) -> Report {
+        match self.take_denied() {
+            Some(action) => eyre!(
+                "{} is not allowed by the {} policy",
+                action,
+                self.policy.name()
+            ),
+            None => error.into(),
+        }
+    }
+
+    pub fn select(&mut self, query: &str) -> Result<Vec<Vec<Value>>> {
         if query.is_empty() {
             return Err(eyre!("Empty Query"));
         }
 
+        self.take_denied();
+        let start = Instant::now();
         let mut statement = match self.connection.prepare(query) {
             Ok(statement) => statement,
+            Err(e) if self.denial_pending() => return Err(self.policy_error(e)),
             Err(e) => {
                 return Err(eyre!("SELECT query could not be executed\n{}", e));
             }
@@ -70,59 +266,389 @@ impl SqlSession {
 
         let column_count = statement.column_count();
         let mut rows = statement.query([])?;
-        let mut result_rows: Vec<Vec<String>> = Vec::new();
+        let mut result_rows: Vec<Vec<Value>> = Vec::new();
 
         while let Some(row) = rows.next()? {
-            let mut result_row: Vec<String> = Vec::new();
+            let mut result_row: Vec<Value> = Vec::with_capacity(column_count);
             for i in 0..column_count {
-                let value = row.get_ref(i)?;
-                result_row.push(match value {
-                    ValueRef::Null => "NULL".to_string(),
-                    ValueRef::Integer(i) => i.to_string(),
-                    ValueRef::Real(f) => f.to_string(),
-                    ValueRef::Text(t) => String::from_utf8_lossy(t).to_string(),
-                    ValueRef::Blob(b) => String::from_utf8_lossy(b).to_string(),
-                });
+                result_row.push(row.get_ref(i)?.into());
             }
             result_rows.push(result_row);
         }
+        drop(rows);
+
+        let mut stats = QueryStats::from_statement(&statement, start.elapsed());
+        stats.rows_returned = result_rows.len();
+        stats.last_insert_rowid = self.connection.last_insert_rowid();
+        self.last_stats = Some(stats);
 
         Ok(result_rows)
     }
 
+    pub fn explain_query_plan(&self, query: &str) -> Result<Vec<PlanRow>> {
+        if query.is_empty() {
+            return Err(eyre!("Empty Query"));
+        }
+
+        let mut statement = self
+            .connection
+            .prepare(&format!("EXPLAIN QUERY PLAN {}", query))?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok(PlanRow {
+                    id: row.get(0)?,
+                    parent: row.get(1)?,
+                    detail: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<PlanRow>>>()?;
+
+        Ok(rows)
+    }
+
+    /// checks a statement against the guard rules before it is run, estimating what it would
+    /// affect with the matching `SELECT count(*)`
+    pub fn assess(&self, query: &str) -> Option<GuardWarning> {
+        if self.guard.mode == GuardMode::Off {
+            return None;
+        }
+        let risk = guard::detect(query).filter(|risk| self.guard.rules.contains(&risk.rule))?;
+        let affected = risk.count_query.as_ref().and_then(|count_query| {
+            self.connection
+                .query_row(count_query, [], |row| row.get::<_, i64>(0))
+                .ok()
+        });
+        // dropping a table or column destroys schema however little data it holds
+        let counts_rows = matches!(
+            risk.rule,
+            GuardRule::UnfilteredDelete | GuardRule::UnfilteredUpdate
+        );
+        if counts_rows && affected.is_some_and(|affected| affected < self.guard.min_rows) {
+            return None;
+        }
+        Some(GuardWarning { risk, affected })
+    }
+
     pub fn execute(&mut self, query: &str) -> Result<usize> {
         if query.is_empty() {
             return Err(eyre!("Empty Query"));
         }
 
-        // check if it's read only check for write operations and exit early
-        if self.read_only {
+        // preparing first lets the authorizer refuse a statement before a transaction is opened
+        self.take_denied();
+        let mut statement = self
+            .connection
+            .prepare(query)
+            .map_err(|e| self.policy_error(e))?;
+
+        // VACUUM can't run inside a transaction, so it runs on its own and takes effect at once
+        let vacuum = guard::is_vacuum(query);
+        if vacuum && self.transaction_active {
             return Err(eyre!(
-                "Attempted an {} operation on a read only database",
-                query.trim_start().to_uppercase().as_str()
+                "Commit or roll back the open transaction before running VACUUM"
             ));
         }
 
-        if !self.transaction_active {
-            self.connection.execute("BEGIN IMMEDIATE", [])?;
+        // a read-only connection cannot take the write lock, and the engine refuses writes anyway
+        if !self.transaction_active && !self.read_only && !vacuum {
+            self.snapshot = self.begin()?;
+            match self.new_recorder() {
+                Ok(recorder) => self.recorder = recorder,
+                Err(e) => {
+                    self.connection.execute("ROLLBACK", [])?;
+                    discard_snapshot(self.snapshot.take());
+                    return Err(e);
+                }
+            }
             self.transaction_active = true;
         }
 
-        let changes = self.connection.execute(query, [])?;
+        if !vacuum {
+            self.pending_statements += 1;
+        }
+        let start = Instant::now();
+        let changes = statement.execute([]).map_err(|e| self.policy_error(e))?;
+
+        let mut stats = QueryStats::from_statement(&statement, start.elapsed());
+        stats.rows_changed = changes;
+        stats.last_insert_rowid = self.connection.last_insert_rowid();
+        self.last_stats = Some(stats);
+        drop(statement);
+
+        self.schema.refresh_if_changed(&self.connection)?;
         Ok(changes)
     }
 
-    pub fn commit(&mut self) {
+    /// how many statements are waiting to be committed, or None when no transaction is open
+    pub fn pending_statements(&self) -> Option<usize> {
+        self.transaction_active.then_some(self.pending_statements)
+    }
+
+    /// the database file behind the connection, which in-memory databases do not have
+    fn database_path(&self) -> Result<PathBuf> {
+        self.connection
+            .path()
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .ok_or_else(|| eyre!("The database is not backed by a file"))
+    }
+
+    pub fn apply_settings(&mut self, settings: &SessionSettings) {
+        self.guard = settings.guard.clone();
+        self.snapshots = settings.snapshots.clone();
+        if settings.journal {
+            self.enable_journal();
+        }
+    }
+
+    /// journals committed changes for this database in a file next to it; read-only sessions
+    /// never commit, databases without a file have nowhere to keep one, and without the `undo`
+    /// feature there is nothing to record the changes with
+    pub fn enable_journal(&mut self) {
+        if self.read_only || !cfg!(feature = "undo") {
+            return;
+        }
+        self.journal = self
+            .database_path()
+            .ok()
+            .map(|database| Journal::for_database(&database));
+    }
+
+    /// begins a write transaction, snapshotting the database first when snapshots are on; the
+    /// copy is taken through this connection before it holds any lock, so however much the
+    /// transaction goes on to write it cannot keep the snapshot from being taken
+    fn begin(&self) -> Result<Option<PathBuf>> {
+        let snapshot = match (&self.snapshots, self.database_path()) {
+            // an in-memory database has no file to copy
+            (Some(snapshots), Ok(database)) => Some(
+                snapshots
+                    .take(&self.connection, &database)
+                    .map_err(|e| eyre!("Could not take a snapshot before writing: {}", e))?,
+            ),
+            _ => None,
+        };
+        if let Err(e) = self.connection.execute("BEGIN IMMEDIATE", []) {
+            discard_snapshot(snapshot);
+            return Err(e.into());
+        }
+        Ok(snapshot)
+    }
+
+    /// a recorder for the transaction that was just begun, when the journal is enabled
+    fn new_recorder(&self) -> Result<Option<Recorder>> {
+        if self.journal.is_none() {
+            return Ok(None);
+        }
+        // SAFETY: `recorder` is declared before `connection`, so it is dropped first
+        unsafe { Recorder::start(&self.connection) }
+            .map(Some)
+            .map_err(|e| eyre!("Could not record changes for undo: {}", e))
+    }
+
+    /// stages the inverse of the most recent commit in a new transaction; when rows changed
+    /// after that commit nothing is staged and the conflicting rows are returned instead
+    pub fn undo_last_commit(&mut self) -> Result<(JournalEntry, Vec<Conflict>)> {
+        if !cfg!(feature = "undo") {
+            return Err(eyre!(changeset::UNDO_UNSUPPORTED));
+        }
+        if self.transaction_active {
+            return Err(eyre!(
+                "Commit or roll back the open transaction before undoing a commit"
+            ));
+        }
+        let journal = self
+            .journal
+            .as_ref()
+            .ok_or_else(|| eyre!("Undo is off for this database"))?;
+        let entry = journal
+            .last()?
+            .ok_or_else(|| eyre!("There are no commits to undo"))?;
+        let inverse = changeset::invert(&entry.changeset)?;
+
+        self.take_denied();
+        self.snapshot = self.begin()?;
+        let conflicts = match changeset::apply(&self.connection, &inverse) {
+            Ok(conflicts) if conflicts.is_empty() => conflicts,
+            Ok(conflicts) => {
+                self.connection.execute("ROLLBACK", [])?;
+                discard_snapshot(self.snapshot.take());
+                return Ok((entry, conflicts));
+            }
+            Err(e) => {
+                self.connection.execute("ROLLBACK", [])?;
+                discard_snapshot(self.snapshot.take());
+                return Err(self.policy_error(e));
+            }
+        };
+
+        // the undo itself is not recorded, only what is run after it
+        match self.new_recorder() {
+            Ok(recorder) => self.recorder = recorder,
+            Err(e) => {
+                self.connection.execute("ROLLBACK", [])?;
+                discard_snapshot(self.snapshot.take());
+                return Err(e);
+            }
+        }
+        self.transaction_active = true;
+        self.pending_statements = 1;
+        self.undoing = Some(entry.offset);
+        self.schema.refresh_if_changed(&self.connection)?;
+        Ok((entry, conflicts))
+    }
+
+    /// writes the changes of the transaction being committed to the journal
+    fn journal_commit(&mut self, changeset: Vec<u8>) -> Result<()> {
+        let Some(journal) = &self.journal else {
+            return Ok(());
+        };
+        if let Some(offset) = self.undoing.take() {
+            journal.truncate(offset)?;
+        }
+        if !changeset.is_empty() {
+            journal.append(&changeset)?;
+        }
+        Ok(())
+    }
+
+    /// reads the info screen's PRAGMAs afresh
+    pub fn database_info(&self) -> Result<DatabaseInfo> {
+        DatabaseInfo::load(&self.connection, self.database_path().ok().as_deref())
+    }
+
+    pub fn read_pragma(&self, setting: &PragmaSetting) -> Result<String> {
+        setting.read(&self.connection)
+    }
+
+    /// validates and applies a new value for a pragma, returning the value sqlite reports after
+    pub fn set_pragma(&mut self, setting: &PragmaSetting, input: &str) -> Result<String> {
         if self.transaction_active {
-            let _ = self.connection.execute("COMMIT", []);
+            return Err(eyre!(
+                "Commit or roll back the open transaction before changing {}",
+                setting.name
+            ));
+        }
+        let literal = setting.validate(input)?;
+
+        self.take_denied();
+        self.connection
+            .execute_batch(&format!("PRAGMA {} = {}", setting.name, literal))
+            .map_err(|e| self.policy_error(e))?;
+
+        // some pragmas are ignored rather than refused, e.g. page_size on a database with tables
+        let value = setting.read(&self.connection)?;
+        if !value.eq_ignore_ascii_case(&literal) {
+            return Err(eyre!("SQLite kept {} at {}", setting.name, value));
+        }
+        Ok(value)
+    }
+
+    /// how much space each table and index takes up
+    pub fn storage_report(&self) -> Result<StorageReport> {
+        StorageReport::load(&self.connection)
+    }
+
+    /// starts `kind` on a read-only connection of its own, so it checks what has been committed
+    /// while the screen stays responsive; an in-memory database can't be opened twice, so a copy
+    /// of it, open transaction included, is checked instead
+    pub fn start_check(&self, kind: CheckKind) -> Result<CheckRun> {
+        match self.database_path() {
+            Ok(path) => {
+                let connection = Connection::open_with_flags(
+                    path,
+                    OpenFlags::SQLITE_OPEN_READ_ONLY
+                        | OpenFlags::SQLITE_OPEN_URI
+                        | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+                )?;
+                CheckRun::start(connection, kind)
+            }
+            Err(_) => {
+                let mut copy = Connection::open_in_memory()?;
+                // every page in one step, since nothing else can write to it meanwhile
+                Backup::new(&self.connection, &mut copy)?.run_to_completion(
+                    i32::MAX,
+                    Duration::ZERO,
+                    None,
+                )?;
+                CheckRun::start(copy, kind)
+            }
+        }
+    }
+
+    /// snapshots of this database, newest first
+    pub fn list_snapshots(&self) -> Result<Vec<Snapshot>> {
+        match &self.snapshots {
+            Some(snapshots) => snapshots.list(&self.database_path()?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// overwrites the database with a snapshot, first snapshotting the current state so the
+    /// restore can itself be undone
+    pub fn restore_snapshot(&mut self, snapshot: &Path) -> Result<()> {
+        if self.transaction_active {
+            return Err(eyre!(
+                "Commit or roll back the open transaction before restoring a snapshot"
+            ));
+        }
+        if self.policy != WritePolicy::Unrestricted {
+            return Err(eyre!(
+                "Restoring a snapshot is not allowed by the {} policy",
+                self.policy.name()
+            ));
+        }
+
+        let database = self.database_path()?;
+        if let Some(snapshots) = &self.snapshots {
+            snapshots.take(&self.connection, &database)?;
+        }
+        self.connection
+            .restore(MAIN_DB, snapshot, None::<fn(Progress)>)?;
+        if let Some(snapshots) = &self.snapshots {
+            snapshots.prune(&database)?;
+        }
+        self.schema.refresh(&self.connection)
+    }
+
+    /// commits the open transaction; on failure the transaction stays open
+    pub fn commit(&mut self) -> Result<()> {
+        if self.transaction_active {
+            // the recorder stays until COMMIT succeeds, so a failed commit can still be retried
+            // and journaled
+            let changeset = match &mut self.recorder {
+                Some(recorder) => recorder.changeset()?,
+                None => Vec::new(),
+            };
+            self.connection.execute("COMMIT", [])?;
+            self.recorder = None;
             self.transaction_active = false;
+            self.pending_statements = 0;
+            self.unsaved_changes = self.is_in_memory();
+            // the snapshot taken as the transaction began is kept now, so older ones may go
+            if self.snapshot.take().is_some()
+                && let Some(snapshots) = &self.snapshots
+            {
+                snapshots.prune(&self.database_path()?).map_err(|e| {
+                    eyre!("Committed, but old snapshots could not be pruned: {}", e)
+                })?;
+            }
+            self.journal_commit(changeset)
+                .map_err(|e| eyre!("Committed, but the changes could not be journaled: {}", e))?;
         }
+        Ok(())
     }
 
-    pub fn rollback(&mut self) {
+    pub fn rollback(&mut self) -> Result<()> {
         if self.transaction_active {
-            let _ = self.connection.execute("ROLLBACK", []);
+            self.connection.execute("ROLLBACK", [])?;
             self.transaction_active = false;
+            self.pending_statements = 0;
+            self.recorder = None;
+            self.undoing = None;
+            discard_snapshot(self.snapshot.take());
+            // rolled back DDL leaves the cached schema stale
+            self.schema.refresh_if_changed(&self.connection)?;
         }
+        Ok(())
     }
 }
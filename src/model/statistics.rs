@@ -0,0 +1,43 @@
+use rusqlite::{Statement, StatementStatus};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Default)]
+pub struct QueryStats {
+    pub elapsed: Duration,
+    pub rows_returned: usize,
+    pub rows_changed: usize,
+    pub last_insert_rowid: i64,
+    pub full_scan_steps: i32,
+    pub sorts: i32,
+    pub autoindexes: i32,
+    pub vm_steps: i32,
+}
+
+impl QueryStats {
+    /// reads sqlite's counters from a statement that has finished stepping
+    pub fn from_statement(statement: &Statement, elapsed: Duration) -> Self {
+        QueryStats {
+            elapsed,
+            full_scan_steps: statement.get_status(StatementStatus::FullscanStep),
+            sorts: statement.get_status(StatementStatus::Sort),
+            autoindexes: statement.get_status(StatementStatus::AutoIndex),
+            vm_steps: statement.get_status(StatementStatus::VmStep),
+            ..QueryStats::default()
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{:.3} ms, {} rows returned, {} changed, last rowid {}, \
+             full scan steps {}, sorts {}, autoindex {}, vm steps {}",
+            self.elapsed.as_secs_f64() * 1000.0,
+            self.rows_returned,
+            self.rows_changed,
+            self.last_insert_rowid,
+            self.full_scan_steps,
+            self.sorts,
+            self.autoindexes,
+            self.vm_steps
+        )
+    }
+}
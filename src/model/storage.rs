@@ -0,0 +1,169 @@
+use color_eyre::eyre::Result;
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+use crate::model::guard::quote_identifier;
+
+/// the space one table or index takes up; the sizes are None when dbstat is unavailable
+#[derive(Debug, Clone, Default)]
+pub struct ObjectStorage {
+    pub name: String,
+    /// "table" or "index"
+    pub kind: String,
+    /// rows in a table, entries in an index
+    pub rows: Option<i64>,
+    pub pages: Option<i64>,
+    pub bytes: Option<i64>,
+    /// bytes inside those pages that hold nothing
+    pub unused: Option<i64>,
+    /// percentage of pages that are not the page after the one before them as the b-tree is
+    /// walked, so a freshly vacuumed object is close to 0
+    pub fragmentation: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StorageReport {
+    pub objects: Vec<ObjectStorage>,
+    /// whether sqlite was built with the dbstat virtual table; without it only rows are counted
+    pub dbstat: bool,
+}
+
+/// the schema type of every object, and whether each table is WITHOUT ROWID
+fn object_kinds(connection: &Connection) -> rusqlite::Result<HashMap<String, (String, bool)>> {
+    let mut statement = connection.prepare(
+        "SELECT s.name, s.type, coalesce(t.wr, 0)
+         FROM sqlite_schema AS s
+         LEFT JOIN pragma_table_list AS t ON t.schema = 'main' AND t.name = s.name
+         WHERE s.type IN ('table', 'index')",
+    )?;
+    statement
+        .query_map([], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))))?
+        .collect()
+}
+
+/// the percentage of an object's pages that were a jump away from the page before them
+fn fragmentation(pages: i64, jumps: i64) -> f64 {
+    if pages > 1 {
+        jumps as f64 * 100.0 / (pages - 1) as f64
+    } else {
+        0.0
+    }
+}
+
+fn from_dbstat(connection: &Connection) -> rusqlite::Result<Vec<ObjectStorage>> {
+    let kinds = object_kinds(connection)?;
+    // dbstat walks one b-tree at a time in page order, so each name's pages arrive together
+    let mut statement = connection.prepare(
+        "SELECT name, pageno, pagetype, ncell, unused, pgsize FROM dbstat WHERE schema = 'main'",
+    )?;
+    let mut rows = statement.query([])?;
+
+    let mut objects: Vec<ObjectStorage> = Vec::new();
+    // the last page seen while walking the current b-tree, and how often the walk jumped
+    let mut last_page = 0;
+    let mut jumps = 0;
+    while let Some(row) = rows.next()? {
+        let name: String = row.get(0)?;
+        let page: i64 = row.get(1)?;
+        let page_type: String = row.get(2)?;
+        let cells: i64 = row.get(3)?;
+        let unused: i64 = row.get(4)?;
+        let page_size: i64 = row.get(5)?;
+        let (kind, without_rowid) = kinds
+            .get(&name)
+            .cloned()
+            // sqlite_schema itself is not listed in sqlite_schema
+            .unwrap_or_else(|| ("table".to_string(), false));
+
+        if objects.last().is_none_or(|object| object.name != name) {
+            if let Some(previous) = objects.last_mut() {
+                previous.fragmentation = Some(fragmentation(previous.pages.unwrap_or(0), jumps));
+            }
+            objects.push(ObjectStorage {
+                name,
+                kind: kind.clone(),
+                rows: Some(0),
+                pages: Some(0),
+                bytes: Some(0),
+                unused: Some(0),
+                fragmentation: None,
+            });
+            last_page = page - 1;
+            jumps = 0;
+        }
+        let Some(object) = objects.last_mut() else {
+            continue;
+        };
+
+        // rowid tables keep their rows in leaf cells only; index b-trees, which WITHOUT ROWID
+        // tables also use, hold an entry in every cell
+        let holds_rows = match page_type.as_str() {
+            "leaf" => true,
+            "internal" => kind == "index" || without_rowid,
+            _ => false,
+        };
+        if holds_rows {
+            object.rows = object.rows.map(|rows| rows + cells);
+        }
+        object.pages = object.pages.map(|pages| pages + 1);
+        object.bytes = object.bytes.map(|bytes| bytes + page_size);
+        object.unused = object.unused.map(|total| total + unused);
+        if page != last_page + 1 {
+            jumps += 1;
+        }
+        last_page = page;
+    }
+
+    if let Some(last) = objects.last_mut() {
+        last.fragmentation = Some(fragmentation(last.pages.unwrap_or(0), jumps));
+    }
+    Ok(objects)
+}
+
+/// without dbstat, the row count of each table is all that can be had
+fn row_counts(connection: &Connection) -> Result<Vec<ObjectStorage>> {
+    let mut statement =
+        connection.prepare("SELECT name FROM sqlite_schema WHERE type = 'table' ORDER BY name")?;
+    let tables: Vec<String> = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    let mut objects: Vec<ObjectStorage> = Vec::with_capacity(tables.len());
+    for name in tables {
+        let rows = connection
+            .query_row(
+                &format!("SELECT count(*) FROM {}", quote_identifier(&name)),
+                [],
+                |row| row.get(0),
+            )
+            .ok();
+        objects.push(ObjectStorage {
+            name,
+            kind: "table".to_string(),
+            rows,
+            ..ObjectStorage::default()
+        });
+    }
+    Ok(objects)
+}
+
+impl StorageReport {
+    /// measures every table and index, falling back to row counts when dbstat is missing
+    pub fn load(connection: &Connection) -> Result<Self> {
+        match from_dbstat(connection) {
+            Ok(objects) => Ok(StorageReport {
+                objects,
+                dbstat: true,
+            }),
+            Err(rusqlite::Error::SqliteFailure(_, Some(message)))
+                if message.contains("no such table: dbstat") =>
+            {
+                Ok(StorageReport {
+                    objects: row_counts(connection)?,
+                    dbstat: false,
+                })
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Style, Stylize},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Clear, Padding, Paragraph},
+};
+use rusqlite::types::Value;
+use std::path::PathBuf;
+
+use crate::model::file_browser::expand_home;
+use crate::model::inspect::{JsonLine, JsonToken, hex_dump, pretty_json};
+use crate::model::value::display_value;
+use crate::ui::themes::ColorPalette;
+use crate::ui::utils::centered_rect;
+
+#[derive(Debug)]
+pub enum InspectorContent {
+    Text(Vec<String>),
+    Json(Vec<JsonLine>),
+    Hex(Vec<String>),
+}
+
+#[derive(Debug)]
+pub struct CellInspector {
+    pub column: String,
+    pub value: Value,
+    pub content: InspectorContent,
+    /// path being typed when saving a blob to a file
+    pub save_prompt: Option<String>,
+    /// an existing file the blob would replace, waiting for y before it is overwritten
+    pub confirm_overwrite: Option<PathBuf>,
+    /// the first line shown; only the lines that fit are built for each frame, so a large blob
+    /// costs no more to draw than a small one
+    scroll: usize,
+}
+
+impl CellInspector {
+    pub fn new(column: String, value: Value) -> Self {
+        let content = match &value {
+            Value::Blob(bytes) => InspectorContent::Hex(hex_dump(bytes)),
+            Value::Text(text) => match pretty_json(text) {
+                Some(lines) => InspectorContent::Json(lines),
+                None => InspectorContent::Text(text.lines().map(str::to_string).collect()),
+            },
+            other => InspectorContent::Text(vec![display_value(other)]),
+        };
+        CellInspector {
+            column,
+            value,
+            content,
+            save_prompt: None,
+            confirm_overwrite: None,
+            scroll: 0,
+        }
+    }
+
+    fn line_count(&self) -> usize {
+        match &self.content {
+            InspectorContent::Text(lines) | InspectorContent::Hex(lines) => lines.len(),
+            InspectorContent::Json(lines) => lines.len(),
+        }
+    }
+
+    fn kind_label(&self) -> String {
+        match &self.value {
+            Value::Blob(bytes) => format!("BLOB, {} bytes", bytes.len()),
+            Value::Text(text) if matches!(self.content, InspectorContent::Json(_)) => {
+                format!("JSON, {} characters", text.chars().count())
+            }
+            Value::Text(text) => format!("TEXT, {} characters", text.chars().count()),
+            Value::Integer(_) => "INTEGER".to_string(),
+            Value::Real(_) => "REAL".to_string(),
+            Value::Null => "NULL".to_string(),
+        }
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        let max_scroll = self.line_count().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add(lines).min(max_scroll);
+    }
+
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    pub fn open_save_prompt(&mut self) {
+        if matches!(self.value, Value::Blob(_)) {
+            self.save_prompt = Some(String::new());
+        }
+    }
+
+    /// writes the blob to the path typed into the save prompt, or returns None and asks for
+    /// confirmation when a file is already there
+    pub fn save_blob(&mut self) -> std::io::Result<Option<String>> {
+        let path = expand_home(&self.save_prompt.take().unwrap_or_default());
+        if path.exists() {
+            self.confirm_overwrite = Some(path);
+            return Ok(None);
+        }
+        self.write_blob(path).map(Some)
+    }
+
+    pub fn write_blob(&self, path: PathBuf) -> std::io::Result<String> {
+        if let Value::Blob(bytes) = &self.value {
+            std::fs::write(&path, bytes)?;
+        }
+        Ok(path.display().to_string())
+    }
+
+    pub fn render(&self, frame: &mut Frame, theme: &ColorPalette) {
+        let area = centered_rect(80, 80, frame.area());
+        let block = Block::bordered()
+            .title(Line::from(format!(" {} ({}) ", self.column, self.kind_label())).centered())
+            .border_style(Style::default().fg(theme.inner_border))
+            .border_set(border::THICK)
+            .padding(Padding::horizontal(1));
+        let inner = block.inner(area);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block.style(Style::default().bg(theme.background)), area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner);
+
+        let visible = chunks[0].height as usize;
+        let lines: Vec<Line> = match &self.content {
+            InspectorContent::Text(lines) | InspectorContent::Hex(lines) => lines
+                .iter()
+                .skip(self.scroll)
+                .take(visible)
+                .map(|line| Line::from(line.clone()).fg(theme.body_text))
+                .collect(),
+            InspectorContent::Json(lines) => lines
+                .iter()
+                .skip(self.scroll)
+                .take(visible)
+                .map(|line| {
+                    Line::from(
+                        line.iter()
+                            .map(|(token, text)| {
+                                let color = match token {
+                                    JsonToken::Key => theme.header_text,
+                                    JsonToken::String => theme.body_text,
+                                    JsonToken::Number => theme.title,
+                                    JsonToken::Literal => theme.highlight,
+                                    JsonToken::Punctuation | JsonToken::Whitespace => {
+                                        theme.inner_border
+                                    }
+                                };
+                                Span::styled(text.clone(), Style::default().fg(color))
+                            })
+                            .collect::<Vec<Span>>(),
+                    )
+                })
+                .collect(),
+        };
+
+        let wrap = !matches!(self.content, InspectorContent::Hex(_));
+        let mut paragraph = Paragraph::new(lines);
+        if wrap {
+            paragraph = paragraph.wrap(ratatui::widgets::Wrap { trim: false });
+        }
+        frame.render_widget(paragraph, chunks[0]);
+
+        let footer = match (&self.save_prompt, &self.confirm_overwrite) {
+            (_, Some(path)) => format!("{} already exists. Overwrite it? y/n", path.display()),
+            (Some(path), None) => format!("Save blob to: {}", path),
+            (None, None) if matches!(self.value, Value::Blob(_)) => {
+                "j/k scroll  w save blob  q close".to_string()
+            }
+            (None, None) => "j/k scroll  q close".to_string(),
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(footer)).fg(theme.highlight),
+            chunks[1],
+        );
+    }
+}
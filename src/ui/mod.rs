@@ -1,3 +1,5 @@
+pub mod cell_inspector;
+pub mod clipboard;
 pub mod notification_widgets;
 pub mod screens;
 pub mod table;
@@ -20,11 +22,25 @@ use std::format;
 use std::vec;
 
 pub fn ui(frame: &mut Frame, app: &mut App) {
-    let title = Line::from(
+    // in-memory databases are called out so nobody mistakes them for the file they came from,
+    // including the one standing in at the open screen before any file is opened
+    let title = if app.session.is_in_memory() {
+        let contents = match &app.session.scratch_source {
+            Some(source) => format!("scratch copy of {}", source),
+            None => "scratch".to_string(),
+        };
+        format!(
+            "{}({}, in memory: lost on quit unless saved with .save)",
+            TOOL_NAME, contents
+        )
+        .bold()
+        .fg(app.theme.warning)
+    } else {
         format!("{}({})", TOOL_NAME, app.sql_path)
             .bold()
-            .fg(app.theme.title),
-    );
+            .fg(app.theme.title)
+    };
+    let title = Line::from(title);
     let instructions = Line::from(vec![
         " Help ".into(),
         "<C-H>".fg(app.theme.highlight).bold(),
@@ -45,16 +61,39 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
     frame.render_widget(main_block.clone(), main_area);
     let inner_area = main_block.inner(main_area);
 
-    let mut current_screen = std::mem::take(&mut app.screen);
+    let mut screens = std::mem::take(&mut app.screens);
 
-    match &mut current_screen {
-        Screen::Terminal(terminal_screen) => terminal_screen.render(frame, app, inner_area),
-        Screen::Results(results_screen) => results_screen.render(frame, app, inner_area),
-        Screen::Help(help_screen) => help_screen.render(frame, &app.theme),
-        Screen::Exiting(quit_screen) => quit_screen.render(frame, &app.theme),
+    // draw from the topmost full screen upwards so popups stay layered over it
+    let base = screens.iter().rposition(|screen| !screen.is_popup());
+    if base.is_none() {
+        let terminal_screen = std::mem::take(&mut app.terminal);
+        terminal_screen.render(frame, app, inner_area);
+        app.terminal = terminal_screen;
     }
 
-    app.screen = current_screen;
+    for screen in &mut screens[base.unwrap_or(0)..] {
+        match screen {
+            Screen::Results => {
+                let mut results = std::mem::take(&mut app.results);
+                results.render(frame, app, inner_area);
+                app.results = results;
+            }
+            Screen::QueryPlan(query_plan_screen) => {
+                query_plan_screen.render(frame, app, inner_area)
+            }
+            Screen::Help(help_screen) => help_screen.render(frame, &app.theme),
+            Screen::Exiting(quit_screen) => quit_screen.render(frame, &app.theme),
+            Screen::Confirm(confirm_screen) => confirm_screen.render(frame, &app.theme),
+            Screen::Snapshots(snapshot_screen) => snapshot_screen.render(frame, app, inner_area),
+            Screen::Open(open_screen) => open_screen.render(frame, app, inner_area),
+            Screen::Info(info_screen) => info_screen.render(frame, app, inner_area),
+            Screen::Check(check_screen) => check_screen.render(frame, app, inner_area),
+            Screen::Pragmas(pragma_screen) => pragma_screen.render(frame, app, inner_area),
+            Screen::Storage(storage_screen) => storage_screen.render(frame, app, inner_area),
+        }
+    }
+
+    app.screens = screens;
 
     if !app.notifications.list.is_empty() {
         // render and return
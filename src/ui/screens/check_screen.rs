@@ -0,0 +1,237 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, List, ListItem, ListState, Padding, Paragraph, Wrap},
+};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use crate::app::App;
+use crate::model::integrity::{CheckKind, CheckRun, Problem};
+
+/// turns while the check's progress handler keeps being called
+const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+
+/// the problems one table turned up; `table` is None for problems with the file itself
+#[derive(Debug)]
+pub struct TableReport {
+    pub table: Option<String>,
+    pub problems: Vec<Problem>,
+}
+
+/// a line of the report: a table heading, or one of its problems
+enum ReportLine<'a> {
+    Table(&'a TableReport),
+    Problem(&'a TableReport, &'a Problem),
+}
+
+#[derive(Debug)]
+pub struct CheckScreen {
+    pub kind: CheckKind,
+    /// the check while it is still running
+    pub run: Option<CheckRun>,
+    pub started: Instant,
+    /// how long the check took, once it has ended
+    pub elapsed: Option<Duration>,
+    /// problems grouped by table, with those about the file itself first
+    pub reports: Vec<TableReport>,
+    pub cancelled: bool,
+    /// why the check stopped early
+    pub error: Option<String>,
+    pub state: ListState,
+}
+
+impl CheckScreen {
+    pub fn new(kind: CheckKind, run: CheckRun) -> Self {
+        let mut check_screen = CheckScreen {
+            kind,
+            run: Some(run),
+            started: Instant::now(),
+            elapsed: None,
+            reports: Vec::new(),
+            cancelled: false,
+            error: None,
+            state: ListState::default(),
+        };
+        check_screen.poll();
+        check_screen
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.run.is_some()
+    }
+
+    /// interrupts the running check; it ends on a later poll
+    pub fn cancel(&mut self) {
+        if let Some(run) = &self.run {
+            run.cancel();
+            self.cancelled = true;
+        }
+    }
+
+    /// collects the outcome once the check has ended
+    pub fn poll(&mut self) {
+        let Some(outcome) = self.run.as_ref().and_then(|run| run.outcome()) else {
+            return;
+        };
+        self.run = None;
+        self.elapsed = Some(self.started.elapsed());
+        match outcome {
+            Ok(problems) => {
+                let mut by_table: BTreeMap<Option<String>, Vec<Problem>> = BTreeMap::new();
+                for problem in problems {
+                    by_table
+                        .entry(problem.table.clone())
+                        .or_default()
+                        .push(problem);
+                }
+                self.reports = by_table
+                    .into_iter()
+                    .map(|(table, problems)| TableReport { table, problems })
+                    .collect();
+                // a cancel that came too late to stop the check changes nothing
+                self.cancelled = false;
+            }
+            Err(_) if self.cancelled => {}
+            Err(e) => self.error = Some(format!("The check failed: {}", e)),
+        }
+        if !self.reports.is_empty() {
+            self.state.select(Some(0));
+        }
+    }
+
+    fn lines(&self) -> Vec<ReportLine<'_>> {
+        self.reports
+            .iter()
+            .flat_map(|report| {
+                std::iter::once(ReportLine::Table(report)).chain(
+                    report
+                        .problems
+                        .iter()
+                        .map(move |problem| ReportLine::Problem(report, problem)),
+                )
+            })
+            .collect()
+    }
+
+    /// the table and row of the selected problem, when sqlite named both
+    pub fn selected_row(&self) -> Option<(String, i64)> {
+        let lines = self.lines();
+        match self.state.selected().and_then(|i| lines.get(i)) {
+            Some(ReportLine::Problem(report, problem)) => {
+                Some((report.table.clone()?, problem.rowid?))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn next(&mut self) {
+        let len = self.lines().len();
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some((i + 1).min(len.saturating_sub(1))));
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(i.saturating_sub(1)));
+        }
+    }
+
+    fn status(&self) -> String {
+        let elapsed = self.elapsed.unwrap_or_else(|| self.started.elapsed());
+        let problems: usize = self
+            .reports
+            .iter()
+            .map(|report| report.problems.len())
+            .sum();
+        if let Some(run) = &self.run {
+            let spinner = SPINNER[run.steps() as usize % SPINNER.len()];
+            format!("{} Checking... {:.1}s", spinner, elapsed.as_secs_f64())
+        } else if self.cancelled {
+            format!("Cancelled after {:.1}s", elapsed.as_secs_f64())
+        } else if self.error.is_some() {
+            format!("Stopped after {:.1}s", elapsed.as_secs_f64())
+        } else if problems == 0 {
+            format!("No problems found in {:.1}s", elapsed.as_secs_f64())
+        } else {
+            format!(
+                "{} problems, found in {:.1}s",
+                problems,
+                elapsed.as_secs_f64()
+            )
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, app: &App, inner_area: Rect) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Length(2),
+                Constraint::Length(if self.error.is_some() { 2 } else { 0 }),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(inner_area);
+
+        let header = Paragraph::new(Line::from(format!(
+            "PRAGMA {} on {}",
+            self.kind.pragma(),
+            app.sql_path
+        )))
+        .block(Block::default().padding(Padding::horizontal(1)))
+        .fg(theme.header_text)
+        .bold();
+        frame.render_widget(header, chunks[0]);
+
+        let status = Paragraph::new(Line::from(self.status()))
+            .block(Block::default().padding(Padding::new(1, 1, 0, 1)))
+            .fg(theme.highlight);
+        frame.render_widget(status, chunks[1]);
+
+        if let Some(error) = &self.error {
+            let error = Paragraph::new(error.as_str())
+                .wrap(Wrap { trim: true })
+                .block(Block::default().padding(Padding::horizontal(1)))
+                .fg(theme.warning);
+            frame.render_widget(error, chunks[2]);
+        }
+
+        let items: Vec<ListItem> = self
+            .lines()
+            .into_iter()
+            .map(|line| match line {
+                ReportLine::Table(report) => ListItem::new(format!(
+                    "{} ({} problems)",
+                    report.table.as_deref().unwrap_or("Database file"),
+                    report.problems.len()
+                ))
+                .fg(theme.header_text)
+                .bold(),
+                ReportLine::Problem(_, problem) => {
+                    ListItem::new(format!("  {}", problem.message)).fg(theme.body_text)
+                }
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::default().padding(Padding::horizontal(1)))
+            .highlight_style(Style::default().bg(theme.highlight).fg(Color::Black));
+        frame.render_stateful_widget(list, chunks[3], &mut self.state);
+
+        let footer = if self.is_running() {
+            "Esc cancel"
+        } else {
+            "Enter show the row  r run again  q close"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(footer))
+                .block(Block::default().padding(Padding::horizontal(1)))
+                .fg(theme.highlight),
+            chunks[4],
+        );
+    }
+}
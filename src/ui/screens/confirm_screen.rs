@@ -0,0 +1,55 @@
+use ratatui::{
+    Frame,
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Padding, Paragraph, Wrap},
+};
+
+use super::ScreenRenderable;
+use crate::model::guard::GuardWarning;
+use crate::ui::themes::ColorPalette;
+use crate::ui::utils::floating_window;
+
+/// asks before running a statement that broke a guard rule
+#[derive(Debug)]
+pub struct ConfirmScreen {
+    pub query: String,
+    pub warning: GuardWarning,
+}
+
+impl ConfirmScreen {
+    pub fn new(query: String, warning: GuardWarning) -> Self {
+        ConfirmScreen { query, warning }
+    }
+}
+
+impl ScreenRenderable for ConfirmScreen {
+    fn render(&self, frame: &mut Frame, theme: &ColorPalette) {
+        let floating_window_rect = floating_window(frame, theme);
+
+        let risk = &self.warning.risk;
+        let estimate = match self.warning.affected {
+            Some(affected) => format!("This would affect about {} {}.", affected, risk.unit),
+            None if risk.count_query.is_some() => {
+                "The number of affected rows could not be estimated.".to_string()
+            }
+            None => String::new(),
+        };
+        let lines = vec![
+            Line::from(risk.summary.clone()).bold().fg(theme.warning),
+            Line::default(),
+            Line::from(self.query.clone()).fg(theme.body_text),
+            Line::default(),
+            Line::from(estimate).fg(theme.body_text),
+            Line::default(),
+            Line::from("Run it? y/n").bold().fg(theme.body_text),
+        ];
+
+        let dialog = Paragraph::new(lines)
+            .centered()
+            .wrap(Wrap { trim: true })
+            .block(Block::default().padding(Padding::uniform(2)));
+
+        frame.render_widget(dialog, floating_window_rect);
+    }
+}
@@ -1,30 +1,202 @@
 use ratatui::{
     Frame,
-    style::Stylize,
-    widgets::{Block, Padding, Paragraph},
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Padding, Paragraph, Wrap},
 };
 
 use super::ScreenRenderable;
+use crate::actions::{HelpContext, KeyBinding};
 use crate::ui::themes::ColorPalette;
 use crate::ui::utils::floating_window;
 
+const TRANSACTION_GUIDE: &[&str] = &[
+    "The first statement that changes data opens a transaction, so edits are staged instead of written straight to the file.",
+    "Queries run inside the same transaction and see the staged changes.",
+    "Ctrl-S commits the staged changes; Ctrl-R rolls them back.",
+    "While a transaction is open other connections can read the database but not write to it.",
+    "Quitting with staged changes asks whether to commit or roll them back first.",
+];
+
+#[derive(Debug)]
+enum HelpLine {
+    Heading(&'static str),
+    Binding(KeyBinding),
+    Text(&'static str),
+    Blank,
+}
+
+impl HelpLine {
+    fn matches(&self, pattern: &str) -> bool {
+        match self {
+            HelpLine::Binding(binding) => {
+                binding.keys.to_lowercase().contains(pattern)
+                    || binding.description.to_lowercase().contains(pattern)
+            }
+            HelpLine::Text(text) => text.to_lowercase().contains(pattern),
+            HelpLine::Heading(_) | HelpLine::Blank => false,
+        }
+    }
+}
+
 #[derive(Debug, Default)]
-pub struct HelpScreen;
+pub struct HelpScreen {
+    lines: Vec<HelpLine>,
+    /// lowercased text the bindings are narrowed to
+    pub search: Option<String>,
+    /// whether keys are currently being typed into the search
+    pub searching: bool,
+    scroll: u16,
+}
 
 impl HelpScreen {
-    pub fn new() -> Self {
-        HelpScreen
+    /// builds the help from the key binding registry, scrolled to `context`'s section
+    pub fn new(context: HelpContext) -> Self {
+        let mut lines = Vec::new();
+        let mut scroll = 0;
+        for section in HelpContext::ALL {
+            if section == context {
+                scroll = lines.len() as u16;
+            }
+            lines.push(HelpLine::Heading(section.title()));
+            lines.extend(section.key_bindings().into_iter().map(HelpLine::Binding));
+            lines.push(HelpLine::Blank);
+        }
+        lines.push(HelpLine::Heading("Transactions"));
+        lines.extend(TRANSACTION_GUIDE.iter().map(|text| HelpLine::Text(text)));
+
+        HelpScreen {
+            lines,
+            search: None,
+            searching: false,
+            scroll,
+        }
+    }
+
+    /// the lines left after the search, keeping the heading of each section with a match
+    fn visible_lines(&self) -> Vec<&HelpLine> {
+        let Some(pattern) = self.search.as_deref().filter(|pattern| !pattern.is_empty()) else {
+            return self.lines.iter().collect();
+        };
+
+        let mut visible = Vec::new();
+        let mut heading = None;
+        for line in &self.lines {
+            match line {
+                HelpLine::Heading(_) => heading = Some(line),
+                line if line.matches(pattern) => {
+                    if let Some(heading) = heading.take() {
+                        if !visible.is_empty() {
+                            visible.push(&HelpLine::Blank);
+                        }
+                        visible.push(heading);
+                    }
+                    visible.push(line);
+                }
+                _ => {}
+            }
+        }
+        visible
+    }
+
+    pub fn scroll_down(&mut self, lines: u16) {
+        let max_scroll = self.visible_lines().len().saturating_sub(1) as u16;
+        self.scroll = self.scroll.saturating_add(lines).min(max_scroll);
+    }
+
+    pub fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll = 0;
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll = self.visible_lines().len().saturating_sub(1) as u16;
+    }
+
+    pub fn open_search(&mut self) {
+        self.search = Some(String::new());
+        self.searching = true;
+        self.scroll = 0;
+    }
+
+    pub fn push_search(&mut self, c: char) {
+        if let Some(search) = &mut self.search {
+            search.extend(c.to_lowercase());
+            self.scroll = 0;
+        }
+    }
+
+    pub fn pop_search(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.pop();
+            self.scroll = 0;
+        }
+    }
+
+    pub fn clear_search(&mut self) {
+        self.search = None;
+        self.searching = false;
+        self.scroll = 0;
     }
 }
 
 impl ScreenRenderable for HelpScreen {
     fn render(&self, frame: &mut Frame, theme: &ColorPalette) {
         let floating_window_rect = floating_window(frame, theme);
-        let commands = Paragraph::new("HELP")
-            .centered()
-            .block(Block::default().padding(Padding::uniform(2)))
-            .fg(theme.body_text);
+        let inner = Block::default()
+            .padding(Padding::uniform(1))
+            .inner(floating_window_rect);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner);
+
+        let key_width = self
+            .lines
+            .iter()
+            .filter_map(|line| match line {
+                HelpLine::Binding(binding) => Some(binding.keys.len()),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+
+        let lines: Vec<Line> = self
+            .visible_lines()
+            .into_iter()
+            .map(|line| match line {
+                HelpLine::Heading(title) => Line::from(*title).fg(theme.title).bold(),
+                HelpLine::Binding(binding) => Line::from(vec![
+                    Span::styled(
+                        format!("  {:<width$}  ", binding.keys, width = key_width),
+                        Style::default()
+                            .fg(theme.highlight)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(binding.description, Style::default().fg(theme.body_text)),
+                ]),
+                HelpLine::Text(text) => Line::from(format!("  {}", text)).fg(theme.body_text),
+                HelpLine::Blank => Line::default(),
+            })
+            .collect();
+
+        let help = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        frame.render_widget(help, chunks[0]);
 
-        frame.render_widget(commands, floating_window_rect);
+        let footer = match &self.search {
+            Some(search) if self.searching => format!("/{}", search),
+            Some(search) => format!("search: {}  Esc clear  q close", search),
+            None => "j/k scroll  / search  q close".to_string(),
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(footer)).fg(theme.highlight),
+            chunks[1],
+        );
     }
 }
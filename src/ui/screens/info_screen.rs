@@ -0,0 +1,146 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Padding, Paragraph},
+};
+
+use crate::app::App;
+use crate::model::database_info::DatabaseInfo;
+use crate::ui::themes::ColorPalette;
+use crate::ui::utils::format_bytes;
+
+/// width of the label column
+const LABEL_WIDTH: usize = 18;
+/// lines `lines` draws besides one per object type and compile option
+const FIXED_LINES: usize = 18;
+
+#[derive(Debug)]
+pub struct InfoScreen {
+    pub info: DatabaseInfo,
+    scroll: u16,
+}
+
+fn section<'a>(title: &'a str, theme: &ColorPalette) -> Line<'a> {
+    Line::from(title).fg(theme.header_text).bold()
+}
+
+fn field<'a>(label: &str, value: String, theme: &ColorPalette) -> Line<'a> {
+    Line::from(vec![
+        Span::styled(
+            format!("  {:<width$}", label, width = LABEL_WIDTH),
+            Style::default().fg(theme.highlight),
+        ),
+        Span::styled(value, Style::default().fg(theme.body_text)),
+    ])
+}
+
+impl InfoScreen {
+    pub fn new(info: DatabaseInfo) -> Self {
+        InfoScreen { info, scroll: 0 }
+    }
+
+    /// swaps in freshly read info, keeping the scroll position
+    pub fn refresh(&mut self, info: DatabaseInfo) {
+        self.info = info;
+    }
+
+    fn lines(&self, theme: &ColorPalette) -> Vec<Line<'_>> {
+        let info = &self.info;
+        let file_size = match info.file_size {
+            Some(size) => format_bytes(size),
+            None => "in memory".to_string(),
+        };
+        let mut lines = vec![
+            section("Storage", theme),
+            field("File size", file_size, theme),
+            field("Page size", format_bytes(info.page_size as u64), theme),
+            field("Page count", info.page_count.to_string(), theme),
+            field(
+                "Freelist pages",
+                format!(
+                    "{} ({})",
+                    info.freelist_count,
+                    format_bytes((info.freelist_count * info.page_size) as u64)
+                ),
+                theme,
+            ),
+            field("Journal mode", info.journal_mode.clone(), theme),
+            field("Auto vacuum", info.auto_vacuum.clone(), theme),
+            field("Encoding", info.encoding.clone(), theme),
+            Line::from(""),
+            section("Header", theme),
+            field("user_version", info.user_version.to_string(), theme),
+            // application ids are usually four ASCII characters, so hex reads better
+            field(
+                "application_id",
+                format!("{} (0x{:08x})", info.application_id, info.application_id),
+                theme,
+            ),
+            Line::from(""),
+            section("Schema", theme),
+        ];
+        lines.extend(
+            info.object_counts
+                .iter()
+                .map(|(label, count)| field(label, count.to_string(), theme)),
+        );
+        lines.push(Line::from(""));
+        lines.push(section("Library", theme));
+        lines.push(field("SQLite version", info.sqlite_version.clone(), theme));
+        lines.push(field(
+            "Compile options",
+            info.compile_options.len().to_string(),
+            theme,
+        ));
+        lines.extend(
+            info.compile_options
+                .iter()
+                .map(|option| Line::from(format!("    {}", option)).fg(theme.body_text)),
+        );
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, app: &App, inner_area: Rect) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(inner_area);
+
+        let header = Paragraph::new(Line::from(format!("Database info for {}", app.sql_path)))
+            .block(Block::default().padding(Padding::horizontal(1)))
+            .fg(theme.header_text)
+            .bold();
+        frame.render_widget(header, chunks[0]);
+
+        let body = Paragraph::new(self.lines(theme))
+            .block(Block::default().padding(Padding::horizontal(1)))
+            .scroll((self.scroll, 0));
+        frame.render_widget(body, chunks[1]);
+
+        frame.render_widget(
+            Paragraph::new(Line::from("r refresh  j/k scroll  q close"))
+                .block(Block::default().padding(Padding::horizontal(1)))
+                .fg(theme.highlight),
+            chunks[2],
+        );
+    }
+
+    pub fn scroll_down(&mut self) {
+        let line_count =
+            FIXED_LINES + self.info.object_counts.len() + self.info.compile_options.len();
+        if (self.scroll as usize) < line_count {
+            self.scroll += 1;
+        }
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+}
@@ -1,25 +1,52 @@
 use crate::ui::themes::ColorPalette;
 use ratatui::Frame;
+pub mod check_screen;
+use crate::ui::screens::check_screen::CheckScreen;
+pub mod confirm_screen;
+use crate::ui::screens::confirm_screen::ConfirmScreen;
 pub mod help_screen;
+pub mod info_screen;
 use crate::ui::screens::help_screen::HelpScreen;
+use crate::ui::screens::info_screen::InfoScreen;
 pub mod quit_screen;
 use crate::ui::screens::quit_screen::QuitScreen;
-pub mod terminal_screen;
-use crate::ui::screens::terminal_screen::TerminalScreen;
+pub mod open_screen;
+use crate::ui::screens::open_screen::OpenScreen;
+pub mod pragma_screen;
+use crate::ui::screens::pragma_screen::PragmaScreen;
+pub mod query_plan_screen;
 pub mod results_screen;
-use crate::ui::screens::results_screen::ResultsScreen;
+pub mod results_tabs;
+pub mod snapshot_screen;
+use crate::ui::screens::snapshot_screen::SnapshotScreen;
+pub mod storage_screen;
+use crate::ui::screens::storage_screen::StorageScreen;
+pub mod terminal_screen;
+use crate::ui::screens::query_plan_screen::QueryPlanScreen;
 
 #[derive(Debug)]
 pub enum Screen {
-    Terminal(TerminalScreen),
-    Results(ResultsScreen),
+    /// shows the result tabs held on `App`
+    Results,
+    QueryPlan(QueryPlanScreen),
     Help(HelpScreen),
     Exiting(QuitScreen),
+    Confirm(ConfirmScreen),
+    Snapshots(SnapshotScreen),
+    Open(OpenScreen),
+    Info(InfoScreen),
+    Check(CheckScreen),
+    Pragmas(PragmaScreen),
+    Storage(StorageScreen),
 }
 
-impl Default for Screen {
-    fn default() -> Self {
-        Screen::Terminal(TerminalScreen::default())
+impl Screen {
+    /// popups are drawn over the screen beneath them instead of replacing it
+    pub fn is_popup(&self) -> bool {
+        matches!(
+            self,
+            Screen::Help(_) | Screen::Exiting(_) | Screen::Confirm(_)
+        )
     }
 }
 
@@ -0,0 +1,271 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph, Wrap},
+};
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::app::App;
+use crate::model::file_browser::{
+    EntryKind, FileEntry, complete_path, expand_home, list_directory,
+};
+use crate::model::policy::WritePolicy;
+
+/// which part of the open screen receives keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenFocus {
+    Path,
+    Recent,
+    Files,
+}
+
+#[derive(Debug)]
+pub struct OpenScreen {
+    /// the path being typed, starting with the one that failed to open
+    pub path: String,
+    pub policy: WritePolicy,
+    /// why the last attempt to open `path` failed
+    pub error: Option<String>,
+    /// whether the user is being asked to create the missing file
+    pub confirm_create: bool,
+    /// whether there is a database to go back to; without one, leaving the screen quits
+    pub can_cancel: bool,
+    pub focus: OpenFocus,
+    /// the directory shown in the file browser
+    pub directory: PathBuf,
+    /// subdirectories and SQLite databases in `directory`
+    pub entries: Vec<FileEntry>,
+    pub files_state: ListState,
+    pub recent: Vec<String>,
+    pub recent_state: ListState,
+}
+
+impl OpenScreen {
+    pub fn new(path: String, policy: WritePolicy, can_cancel: bool, recent: Vec<String>) -> Self {
+        // browse the directory of the path, or the working directory when it has none
+        let expanded = expand_home(&path);
+        let directory = expanded
+            .parent()
+            .filter(|directory| !directory.as_os_str().is_empty() && directory.is_dir())
+            .map(Path::to_path_buf)
+            .or_else(|| env::current_dir().ok())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        let mut recent_state = ListState::default();
+        if !recent.is_empty() {
+            recent_state.select(Some(0));
+        }
+        let mut open_screen = OpenScreen {
+            path,
+            policy,
+            error: None,
+            confirm_create: false,
+            can_cancel,
+            focus: OpenFocus::Path,
+            directory: PathBuf::new(),
+            entries: Vec::new(),
+            files_state: ListState::default(),
+            recent,
+            recent_state,
+        };
+        open_screen.browse(directory);
+        open_screen
+    }
+
+    /// shows `directory` in the file browser
+    pub fn browse(&mut self, directory: PathBuf) {
+        let directory = directory.canonicalize().unwrap_or(directory);
+        match list_directory(&directory) {
+            Ok(entries) => {
+                self.entries = entries;
+                self.directory = directory;
+                self.files_state
+                    .select((!self.entries.is_empty()).then_some(0));
+            }
+            Err(e) => self.error = Some(format!("Could not read {}: {}", directory.display(), e)),
+        }
+    }
+
+    pub fn browse_parent(&mut self) {
+        if let Some(parent) = self.directory.parent() {
+            self.browse(parent.to_path_buf());
+        }
+    }
+
+    pub fn selected_entry(&self) -> Option<&FileEntry> {
+        self.files_state
+            .selected()
+            .and_then(|i| self.entries.get(i))
+    }
+
+    pub fn selected_recent(&self) -> Option<&String> {
+        self.recent_state
+            .selected()
+            .and_then(|i| self.recent.get(i))
+    }
+
+    fn focused_list(&mut self) -> Option<(&mut ListState, usize)> {
+        match self.focus {
+            OpenFocus::Path => None,
+            OpenFocus::Recent => Some((&mut self.recent_state, self.recent.len())),
+            OpenFocus::Files => Some((&mut self.files_state, self.entries.len())),
+        }
+    }
+
+    pub fn next(&mut self) {
+        if let Some((state, len)) = self.focused_list()
+            && let Some(i) = state.selected()
+        {
+            state.select(Some((i + 1).min(len.saturating_sub(1))));
+        }
+    }
+
+    /// moves up the focused list, back to the path from its first entry
+    pub fn previous(&mut self) {
+        match self.focused_list() {
+            Some((state, _)) if state.selected().is_some_and(|i| i > 0) => {
+                state.select(state.selected().map(|i| i - 1));
+            }
+            _ => self.focus = OpenFocus::Path,
+        }
+    }
+
+    /// switches between the recent list and the file browser
+    pub fn switch_list(&mut self) {
+        self.focus = match self.focus {
+            OpenFocus::Files if !self.recent.is_empty() => OpenFocus::Recent,
+            _ => OpenFocus::Files,
+        };
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.path.push(c);
+        self.error = None;
+    }
+
+    pub fn pop_char(&mut self) {
+        self.path.pop();
+        self.error = None;
+    }
+
+    /// completes the typed path, browsing into a directory once it is complete
+    pub fn complete(&mut self) {
+        let Some(completed) = complete_path(&self.path) else {
+            return;
+        };
+        self.path = completed;
+        self.error = None;
+        let expanded = expand_home(&self.path);
+        if self.path.ends_with(std::path::MAIN_SEPARATOR) && expanded.is_dir() {
+            self.browse(expanded);
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, app: &App, inner_area: Rect) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Length(2),
+                Constraint::Length(if self.error.is_some() { 2 } else { 0 }),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(inner_area);
+
+        let header = Paragraph::new(Line::from(format!(
+            "Open a database ({})",
+            self.policy.name()
+        )))
+        .block(Block::default().padding(Padding::horizontal(1)))
+        .fg(theme.header_text)
+        .bold();
+        frame.render_widget(header, chunks[0]);
+
+        let cursor = if self.focus == OpenFocus::Path && !self.confirm_create {
+            "█"
+        } else {
+            ""
+        };
+        let input = Paragraph::new(Line::from(vec![
+            Span::from("Path: ").fg(theme.highlight).bold(),
+            Span::from(self.path.as_str()).fg(theme.body_text),
+            Span::from(cursor).fg(theme.highlight),
+        ]))
+        .block(Block::default().padding(Padding::horizontal(1)));
+        frame.render_widget(input, chunks[1]);
+
+        if let Some(error) = &self.error {
+            let error = Paragraph::new(error.as_str())
+                .wrap(Wrap { trim: true })
+                .block(Block::default().padding(Padding::horizontal(1)))
+                .fg(theme.warning);
+            frame.render_widget(error, chunks[2]);
+        }
+
+        let lists = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(chunks[3]);
+        let list_block = |title: String, focused: bool| {
+            Block::default()
+                .borders(Borders::TOP)
+                .title(title)
+                .padding(Padding::horizontal(1))
+                .border_style(Style::default().fg(if focused {
+                    theme.highlight
+                } else {
+                    theme.inner_border
+                }))
+        };
+        let highlight = |focused: bool| {
+            if focused {
+                Style::default().bg(theme.highlight).fg(Color::Black)
+            } else {
+                Style::default()
+            }
+        };
+
+        let recent_focused = self.focus == OpenFocus::Recent;
+        let recent = List::new(self.recent.iter().map(|path| ListItem::new(path.as_str())))
+            .block(list_block(" Recent ".to_string(), recent_focused))
+            .style(Style::default().fg(theme.body_text))
+            .highlight_style(highlight(recent_focused));
+        frame.render_stateful_widget(recent, lists[0], &mut self.recent_state);
+
+        let files_focused = self.focus == OpenFocus::Files;
+        let files = List::new(self.entries.iter().map(|entry| match entry.kind {
+            EntryKind::Parent | EntryKind::Directory => {
+                ListItem::new(format!("{}/", entry.name)).fg(theme.header_text)
+            }
+            EntryKind::Database => ListItem::new(entry.name.as_str()),
+        }))
+        .block(list_block(
+            format!(" {} ", self.directory.display()),
+            files_focused,
+        ))
+        .style(Style::default().fg(theme.body_text))
+        .highlight_style(highlight(files_focused));
+        frame.render_stateful_widget(files, lists[1], &mut self.files_state);
+
+        let leave = if self.can_cancel { "go back" } else { "quit" };
+        let footer = if self.confirm_create {
+            format!("Create a new database at {}? y/n", self.path)
+        } else if self.focus == OpenFocus::Path {
+            format!("Enter open  Tab complete  Down browse  Esc {}", leave)
+        } else {
+            "Enter open  Backspace parent directory  Tab switch list  Esc back to the path"
+                .to_string()
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(footer))
+                .block(Block::default().padding(Padding::horizontal(1)))
+                .fg(theme.highlight),
+            chunks[4],
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Cell, Padding, Paragraph, Row, Table, TableState, Wrap},
+};
+
+use crate::app::App;
+use crate::model::pragma_settings::{PRAGMA_SETTINGS, PragmaKind, PragmaScope, PragmaSetting};
+use crate::model::sql_session::SqlSession;
+
+#[derive(Debug)]
+pub struct PragmaScreen {
+    /// the current value of each of `PRAGMA_SETTINGS`, or why it could not be read
+    pub values: Vec<String>,
+    pub state: TableState,
+    /// the new value being typed for the selected pragma
+    pub input: Option<String>,
+    /// why the last change was refused
+    pub error: Option<String>,
+}
+
+impl PragmaScreen {
+    pub fn new(session: &SqlSession) -> Self {
+        let mut pragma_screen = PragmaScreen {
+            values: Vec::new(),
+            state: TableState::default().with_selected(Some(0)),
+            input: None,
+            error: None,
+        };
+        pragma_screen.refresh(session);
+        pragma_screen
+    }
+
+    /// reads every value again, since other statements can change them too
+    pub fn refresh(&mut self, session: &SqlSession) {
+        self.values = PRAGMA_SETTINGS
+            .iter()
+            .map(|setting| {
+                session
+                    .read_pragma(setting)
+                    .unwrap_or_else(|e| format!("error: {}", e))
+            })
+            .collect();
+    }
+
+    pub fn selected(&self) -> Option<&'static PragmaSetting> {
+        self.state.selected().and_then(|i| PRAGMA_SETTINGS.get(i))
+    }
+
+    pub fn next(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state
+                .select(Some((i + 1).min(PRAGMA_SETTINGS.len() - 1)));
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(i.saturating_sub(1)));
+        }
+    }
+
+    /// starts editing the selected pragma from its current value
+    pub fn edit(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.input = self.values.get(i).cloned();
+            self.error = None;
+        }
+    }
+
+    fn value_cell(&self, i: usize) -> Cell<'static> {
+        match (&self.input, self.state.selected()) {
+            (Some(input), Some(selected)) if selected == i => Cell::from(format!("{}█", input)),
+            _ => Cell::from(self.values.get(i).cloned().unwrap_or_default()),
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, app: &App, inner_area: Rect) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(0),
+                Constraint::Length(if self.error.is_some() { 2 } else { 0 }),
+                Constraint::Length(1),
+            ])
+            .split(inner_area);
+
+        // forgetting foreign_keys is the usual mistake, so it is called out when off
+        let mut header = vec![Span::from(format!("PRAGMA settings for {}", app.sql_path)).bold()];
+        let foreign_keys_off = PRAGMA_SETTINGS
+            .iter()
+            .zip(&self.values)
+            .any(|(setting, value)| setting.name == "foreign_keys" && value == "off");
+        if foreign_keys_off {
+            header.push(Span::from("  foreign keys are not enforced").fg(theme.warning));
+        }
+        frame.render_widget(
+            Paragraph::new(Line::from(header))
+                .block(Block::default().padding(Padding::horizontal(1)))
+                .fg(theme.header_text),
+            chunks[0],
+        );
+
+        let rows: Vec<Row> = PRAGMA_SETTINGS
+            .iter()
+            .enumerate()
+            .map(|(i, setting)| {
+                // values kept in the file outlive the session, so they stand out
+                let scope_style = match setting.scope {
+                    PragmaScope::File => Style::default().fg(theme.warning),
+                    PragmaScope::Connection => Style::default().fg(theme.body_text),
+                };
+                Row::new(vec![
+                    Cell::from(setting.name),
+                    self.value_cell(i),
+                    Cell::from(setting.scope.name()).style(scope_style),
+                    Cell::from(setting.description),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(20),
+                Constraint::Length(14),
+                Constraint::Length(12),
+                Constraint::Min(0),
+            ],
+        )
+        .header(
+            Row::new(vec!["Pragma", "Value", "Kept in", "Description"])
+                .style(
+                    Style::default()
+                        .fg(theme.header_text)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .bottom_margin(1),
+        )
+        .block(Block::default().padding(Padding::horizontal(1)))
+        .style(Style::default().fg(theme.body_text))
+        .row_highlight_style(Style::default().bg(theme.highlight).fg(Color::Black))
+        .highlight_symbol(">> ");
+        frame.render_stateful_widget(table, chunks[1], &mut self.state);
+
+        if let Some(error) = &self.error {
+            let error = Paragraph::new(error.as_str())
+                .wrap(Wrap { trim: true })
+                .block(Block::default().padding(Padding::horizontal(1)))
+                .fg(theme.warning);
+            frame.render_widget(error, chunks[2]);
+        }
+
+        let footer = match (self.input.is_some(), self.selected().map(|s| s.kind)) {
+            (true, Some(PragmaKind::Choice(choices))) => {
+                format!("{}  Enter apply  Esc cancel", choices.join(" / "))
+            }
+            (true, Some(PragmaKind::Boolean)) => "on / off  Enter apply  Esc cancel".to_string(),
+            (true, _) => "Enter apply  Esc cancel".to_string(),
+            (false, _) => "Enter edit  r refresh  q close".to_string(),
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(footer))
+                .block(Block::default().padding(Padding::horizontal(1)))
+                .fg(theme.highlight),
+            chunks[3],
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Padding, Paragraph},
+};
+
+use crate::app::App;
+use crate::model::query_plan::PlanLine;
+
+#[derive(Debug, Default)]
+pub struct QueryPlanScreen {
+    pub query: String,
+    pub lines: Vec<PlanLine>,
+    scroll: u16,
+}
+
+impl QueryPlanScreen {
+    pub fn new(query: String, lines: Vec<PlanLine>) -> Self {
+        QueryPlanScreen {
+            query,
+            lines,
+            scroll: 0,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, app: &App, inner_area: Rect) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(inner_area);
+
+        let warning_count = self.lines.iter().filter(|line| line.warning).count();
+        let header = Paragraph::new(vec![
+            Line::from(self.query.clone()).fg(theme.header_text).bold(),
+            Line::from(format!(
+                "{} plan steps, {} warnings",
+                self.lines.len(),
+                warning_count
+            )),
+        ])
+        .block(Block::default().padding(Padding::horizontal(1)))
+        .fg(theme.body_text);
+        frame.render_widget(header, chunks[0]);
+
+        let tree: Vec<Line> = std::iter::once(Line::from("QUERY PLAN").bold())
+            .chain(self.lines.iter().map(|line| {
+                let detail_style = if line.warning {
+                    Style::default()
+                        .fg(theme.warning)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.body_text)
+                };
+                Line::from(vec![
+                    Span::styled(line.prefix.clone(), Style::default().fg(theme.inner_border)),
+                    Span::styled(line.detail.clone(), detail_style),
+                ])
+            }))
+            .collect();
+
+        let tree_paragraph = Paragraph::new(tree)
+            .block(Block::default().padding(Padding::horizontal(1)))
+            .fg(theme.body_text)
+            .scroll((self.scroll, 0));
+        frame.render_widget(tree_paragraph, chunks[1]);
+    }
+
+    pub fn scroll_down(&mut self) {
+        if (self.scroll as usize) < self.lines.len() {
+            self.scroll += 1;
+        }
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+}
@@ -1,20 +1,100 @@
 use ratatui::{
     Frame,
-    style::Stylize,
-    widgets::{Block, Padding, Paragraph},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Padding, Paragraph, Wrap},
 };
 
 use super::ScreenRenderable;
 use crate::app::TOOL_NAME;
+use crate::model::policy::WritePolicy;
 use crate::ui::themes::ColorPalette;
 use crate::ui::utils::floating_window;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuitChoice {
+    CommitAndQuit,
+    RollbackAndQuit,
+    Cancel,
+}
+
+impl QuitChoice {
+    pub const ALL: [QuitChoice; 3] = [
+        QuitChoice::CommitAndQuit,
+        QuitChoice::RollbackAndQuit,
+        QuitChoice::Cancel,
+    ];
+
+    fn label(&self, leaving: &Leaving) -> String {
+        let verb = match leaving {
+            Leaving::Quit => "quit",
+            Leaving::Open { .. } => "open",
+        };
+        match self {
+            QuitChoice::CommitAndQuit => format!("[c] Commit and {}", verb),
+            QuitChoice::RollbackAndQuit => format!("[r] Rollback and {}", verb),
+            QuitChoice::Cancel => "[n] Cancel".to_string(),
+        }
+    }
+}
+
+/// what happens once the open transaction is dealt with
+#[derive(Debug, Clone, Default)]
+pub enum Leaving {
+    #[default]
+    Quit,
+    /// switch to another database
+    Open {
+        path: String,
+        policy: WritePolicy,
+        create: bool,
+    },
+}
+
 #[derive(Debug, Default)]
-pub struct QuitScreen;
+pub struct QuitScreen {
+    /// statements staged in the open transaction, None when there is no transaction to settle
+    pub pending_statements: Option<usize>,
+    /// whether the database is in memory with changes that leaving would lose
+    pub unsaved: bool,
+    pub leaving: Leaving,
+    selected: usize,
+}
 
 impl QuitScreen {
-    pub fn new() -> Self {
-        QuitScreen
+    pub fn new(pending_statements: Option<usize>, unsaved: bool) -> Self {
+        QuitScreen {
+            pending_statements,
+            unsaved,
+            leaving: Leaving::Quit,
+            selected: 0,
+        }
+    }
+
+    /// asks what to do with the open transaction or unsaved database before switching databases
+    pub fn before_opening(
+        pending_statements: Option<usize>,
+        unsaved: bool,
+        leaving: Leaving,
+    ) -> Self {
+        QuitScreen {
+            pending_statements,
+            unsaved,
+            leaving,
+            selected: 0,
+        }
+    }
+
+    pub fn selected(&self) -> QuitChoice {
+        QuitChoice::ALL[self.selected]
+    }
+
+    pub fn next_choice(&mut self) {
+        self.selected = (self.selected + 1) % QuitChoice::ALL.len();
+    }
+
+    pub fn previous_choice(&mut self) {
+        self.selected = (self.selected + QuitChoice::ALL.len() - 1) % QuitChoice::ALL.len();
     }
 }
 
@@ -22,12 +102,72 @@ impl ScreenRenderable for QuitScreen {
     fn render(&self, frame: &mut Frame, theme: &ColorPalette) {
         let floating_window_rect = floating_window(frame, theme);
 
-        let confirmation = Paragraph::new(format!("Quit {} Session? y/n", TOOL_NAME))
+        let question = match &self.leaving {
+            Leaving::Quit => format!("Quit {} Session?", TOOL_NAME),
+            Leaving::Open { path, .. } => format!("Open {}?", path),
+        };
+        let Some(pending) = self.pending_statements else {
+            let lines = if self.unsaved {
+                let without_saving = match &self.leaving {
+                    Leaving::Quit => "[y] Quit without saving",
+                    Leaving::Open { .. } => "[y] Open without saving",
+                };
+                vec![
+                    Line::from(question).bold(),
+                    Line::default(),
+                    Line::from("The in-memory database has changes that were never saved."),
+                    Line::default(),
+                    Line::from("[s] Save a copy first"),
+                    Line::from(without_saving),
+                    Line::from("[n] Cancel"),
+                ]
+            } else {
+                vec![Line::from(format!("{} y/n", question)).bold()]
+            };
+            let confirmation = Paragraph::new(lines)
+                .centered()
+                .wrap(Wrap { trim: true })
+                .block(Block::default().padding(Padding::uniform(2)))
+                .fg(theme.body_text);
+
+            frame.render_widget(confirmation, floating_window_rect);
+            return;
+        };
+
+        let statements = if pending == 1 {
+            "statement"
+        } else {
+            "statements"
+        };
+        let mut lines = vec![
+            Line::from(question).bold(),
+            Line::default(),
+            Line::from(format!(
+                "The open transaction has {} uncommitted {}.",
+                pending, statements
+            )),
+        ];
+        if self.unsaved {
+            lines.push(Line::from(
+                "The database is in memory, so committed changes are lost too; s saves a copy first.",
+            ));
+        }
+        lines.push(Line::default());
+        lines.extend(QuitChoice::ALL.iter().map(|choice| {
+            let line = Line::from(choice.label(&self.leaving));
+            if *choice == self.selected() {
+                line.style(Style::default().bg(theme.highlight).fg(Color::Black))
+            } else {
+                line
+            }
+        }));
+
+        let dialog = Paragraph::new(lines)
             .centered()
+            .wrap(Wrap { trim: true })
             .block(Block::default().padding(Padding::uniform(2)))
-            .bold()
             .fg(theme.body_text);
 
-        frame.render_widget(confirmation, floating_window_rect);
+        frame.render_widget(dialog, floating_window_rect);
     }
 }
@@ -1,35 +1,189 @@
 use ratatui::{
     Frame,
-    layout::{Constraint, Margin, Rect},
-    style::{Color, Modifier, Style},
-    widgets::{Block, Cell, Padding, Row, Scrollbar, ScrollbarOrientation, Table},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Cell, Padding, Paragraph, Row, Scrollbar, ScrollbarOrientation, Table},
 };
 
 use crate::app::App;
-use crate::ui::table::TableView;
+use crate::model::filter::RowFilter;
+use crate::model::statistics::QueryStats;
+use crate::ui::cell_inspector::CellInspector;
+use crate::ui::table::{SortDirection, TableView};
+use crate::ui::utils::truncate_to_width;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptKind {
+    Search,
+    Filter,
+    JumpToRow,
+}
+
+#[derive(Debug)]
+pub struct Prompt {
+    pub kind: PromptKind,
+    pub input: String,
+}
 
 #[derive(Debug, Default)]
 pub struct ResultsScreen {
+    pub query: String,
     pub table_view: Option<TableView>,
+    pub stats: Option<QueryStats>,
+    pub prompt: Option<Prompt>,
+    pub inspector: Option<CellInspector>,
+    /// numeric prefix typed before a motion, as in `25j`
+    pub count: Option<usize>,
 }
 
 impl ResultsScreen {
-    pub fn new() -> Self {
-        ResultsScreen { table_view: None }
+    pub fn new(query: String) -> Self {
+        ResultsScreen {
+            query,
+            table_view: None,
+            stats: None,
+            prompt: None,
+            inspector: None,
+            count: None,
+        }
+    }
+
+    pub fn push_count_digit(&mut self, digit: u32) {
+        let count = self.count.unwrap_or(0);
+        self.count = Some(count.saturating_mul(10).saturating_add(digit as usize));
+    }
+
+    /// opens the full value of the selected cell in a popup
+    pub fn open_inspector(&mut self) {
+        let Some(table_view) = &self.table_view else {
+            return;
+        };
+        let (Some(row), Some(column)) = (
+            table_view.state.selected(),
+            table_view.selected_data_column(),
+        ) else {
+            return;
+        };
+        if let (Some(row), Some(name)) = (
+            table_view.data.rows.get(row),
+            table_view.data.columns.get(column),
+        ) && let Some(value) = row.values.get(column)
+        {
+            self.inspector = Some(CellInspector::new(name.clone(), value.clone()));
+        }
+    }
+
+    pub fn open_prompt(&mut self, kind: PromptKind) {
+        self.prompt = Some(Prompt {
+            kind,
+            input: String::new(),
+        });
+    }
+
+    /// applies the prompt text as a search or a filter and closes the prompt
+    pub fn submit_prompt(&mut self) {
+        let (Some(prompt), Some(table_view)) = (self.prompt.take(), &mut self.table_view) else {
+            return;
+        };
+        match prompt.kind {
+            PromptKind::Search => table_view.search(&prompt.input),
+            PromptKind::Filter => {
+                let filter = (!prompt.input.trim().is_empty())
+                    .then(|| RowFilter::parse(&prompt.input, &table_view.data.columns));
+                table_view.set_filter(filter);
+            }
+            PromptKind::JumpToRow => {
+                if let Ok(row_number) = prompt.input.trim().parse::<usize>() {
+                    table_view.go_to_row(row_number);
+                }
+            }
+        }
+    }
+
+    fn status_line(&self, table_view: &TableView) -> String {
+        if let Some(prompt) = &self.prompt {
+            return match prompt.kind {
+                PromptKind::Search => format!("/{}", prompt.input),
+                PromptKind::Filter => format!("filter: {}", prompt.input),
+                PromptKind::JumpToRow => format!(":{}", prompt.input),
+            };
+        }
+
+        let data = &table_view.data;
+        let row = table_view.state.selected().map_or(0, |row| row + 1);
+        let column = table_view
+            .selected_data_column()
+            .and_then(|column| data.columns.get(column))
+            .map_or("", |name| name.as_str());
+        let mut status = format!("row {} of {}, {}", row, data.rows.len(), column);
+        if let Some(filter) = &table_view.filter {
+            status.push_str(&format!(
+                "  (filtered from {} rows: {})",
+                data.total_rows(),
+                filter.description()
+            ));
+        }
+        if let Some(count) = self.count {
+            status.push_str(&format!("  {}", count));
+        }
+        if let Some(search) = &table_view.search {
+            let position = search.current.map_or(0, |current| current + 1);
+            status.push_str(&format!(
+                "  match {} of {} for \"{}\"",
+                position,
+                search.matches.len(),
+                search.pattern
+            ));
+        }
+        status
     }
 
     pub fn render(&mut self, frame: &mut Frame, app: &App, inner_area: Rect) {
+        let status = match &self.table_view {
+            Some(table_view) => self.status_line(table_view),
+            None => return,
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner_area);
+
         if let Some(table_view) = &mut self.table_view {
             let theme = &app.theme;
+            let match_style = Style::default().fg(Color::Black).bg(theme.title);
+            let table_area = chunks[0];
+            // the header row and its bottom margin take two lines
+            table_view.page_height = table_area.height.saturating_sub(2) as usize;
+            let widths = table_view.column_widths(app.max_column_width);
+            let columns = table_view.visible_columns(table_area.width, &widths);
             let data = &table_view.data;
-            // Adjust Constraint::Length by adding padding
-            let constraints: Vec<Constraint> = data
-                .max_lengths
+
+            let constraints: Vec<Constraint> = columns
                 .iter()
-                .map(|length| Constraint::Length(*length as u16 + 2))
+                .map(|position| Constraint::Length(widths[table_view.layout.order[*position]]))
                 .collect();
 
-            let header = Row::new(data.columns.clone())
+            let header_cells: Vec<String> = columns
+                .iter()
+                .map(|position| {
+                    let i = table_view.layout.order[*position];
+                    // the two columns reserved for the sort arrow are only used by the header
+                    let name =
+                        truncate_to_width(&data.columns[i], widths[i].saturating_sub(2) as usize);
+                    match table_view.sort {
+                        Some((column, SortDirection::Ascending)) if column == i => {
+                            format!("{} ▲", name)
+                        }
+                        Some((column, SortDirection::Descending)) if column == i => {
+                            format!("{} ▼", name)
+                        }
+                        _ => name.to_string(),
+                    }
+                })
+                .collect();
+
+            let header = Row::new(header_cells)
                 .style(
                     Style::default()
                         .fg(theme.header_text)
@@ -41,9 +195,19 @@ impl ResultsScreen {
                 .rows
                 .iter()
                 .map(|row_data| {
-                    let cells = row_data
+                    let cells = columns
                         .iter()
-                        .map(|cell_data| Cell::from(cell_data.as_str()))
+                        .map(|position| {
+                            let i = table_view.layout.order[*position];
+                            let cell_data = row_data.get(i).map_or("", |c| c.as_str());
+                            let cell = Cell::from(truncate_to_width(cell_data, widths[i] as usize));
+                            match &table_view.search {
+                                Some(search) if search.cell_matches(cell_data) => {
+                                    cell.style(match_style)
+                                }
+                                _ => cell,
+                            }
+                        })
                         .collect::<Vec<Cell>>();
                     Row::new(cells)
                 })
@@ -53,15 +217,23 @@ impl ResultsScreen {
                 .header(header)
                 .block(Block::default().padding(Padding::horizontal(1)))
                 .row_highlight_style(Style::default().bg(theme.highlight).fg(Color::Black))
+                .cell_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
                 .highlight_symbol(">> ");
 
             let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
                 .begin_symbol(Some("↑"))
                 .end_symbol(Some("↓"));
 
-            let table_area = inner_area;
-
-            frame.render_stateful_widget(table, table_area, &mut table_view.state);
+            // the table only sees the visible columns, so the selection is shifted to match
+            let mut state = table_view.state;
+            state.select_column(
+                table_view
+                    .state
+                    .selected_column()
+                    .and_then(|selected| columns.iter().position(|p| *p == selected)),
+            );
+            frame.render_stateful_widget(table, table_area, &mut state);
+            *table_view.state.offset_mut() = state.offset();
             frame.render_stateful_widget(
                 scrollbar,
                 table_area.inner(Margin {
@@ -70,6 +242,15 @@ impl ResultsScreen {
                 }),
                 &mut table_view.scroll_state,
             );
+
+            let status_paragraph = Paragraph::new(Line::from(status))
+                .block(Block::default().padding(Padding::horizontal(1)))
+                .fg(theme.header_text);
+            frame.render_widget(status_paragraph, chunks[1]);
+        }
+
+        if let Some(inspector) = &self.inspector {
+            inspector.render(frame, &app.theme);
         }
     }
 }
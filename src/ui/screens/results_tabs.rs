@@ -0,0 +1,99 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Padding, Tabs},
+};
+
+use crate::app::App;
+use crate::ui::screens::results_screen::ResultsScreen;
+use crate::ui::utils::truncate_to_width;
+
+const TAB_TITLE_WIDTH: usize = 24;
+
+/// result sets kept in memory after their screen is closed, oldest first
+#[derive(Debug, Default)]
+pub struct ResultTabs {
+    pub tabs: Vec<ResultsScreen>,
+    pub active: usize,
+}
+
+impl ResultTabs {
+    /// adds a result set as the active tab, dropping the oldest past `limit`
+    pub fn open(&mut self, results_screen: ResultsScreen, limit: usize) {
+        self.tabs.push(results_screen);
+        let excess = self.tabs.len().saturating_sub(limit.max(1));
+        self.tabs.drain(..excess);
+        self.active = self.tabs.len() - 1;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    pub fn current(&self) -> Option<&ResultsScreen> {
+        self.tabs.get(self.active)
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut ResultsScreen> {
+        self.tabs.get_mut(self.active)
+    }
+
+    pub fn next_tab(&mut self) {
+        if !self.tabs.is_empty() {
+            self.active = (self.active + 1) % self.tabs.len();
+        }
+    }
+
+    pub fn previous_tab(&mut self) {
+        if !self.tabs.is_empty() {
+            self.active = (self.active + self.tabs.len() - 1) % self.tabs.len();
+        }
+    }
+
+    /// removes the active tab and returns it, selecting the one before it
+    pub fn close_tab(&mut self) -> Option<ResultsScreen> {
+        if self.tabs.is_empty() {
+            return None;
+        }
+        let closed = self.tabs.remove(self.active);
+        self.active = self.active.min(self.tabs.len().saturating_sub(1));
+        Some(closed)
+    }
+
+    fn tab_title(results_screen: &ResultsScreen) -> String {
+        let query = results_screen
+            .query
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let query = truncate_to_width(&query, TAB_TITLE_WIDTH);
+        match &results_screen.stats {
+            Some(stats) => format!("{} ({:.1} ms)", query, stats.elapsed.as_secs_f64() * 1000.0),
+            None => query.to_string(),
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, app: &App, inner_area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(inner_area);
+
+        let tabs = Tabs::new(self.tabs.iter().map(Self::tab_title))
+            .select(self.active)
+            .block(Block::default().padding(Padding::horizontal(1)))
+            .style(Style::default().fg(app.theme.body_text))
+            .highlight_style(
+                Style::default()
+                    .bg(app.theme.highlight)
+                    .fg(Color::Black)
+                    .bold(),
+            );
+        frame.render_widget(tabs, chunks[0]);
+
+        if let Some(results_screen) = self.current_mut() {
+            results_screen.render(frame, app, chunks[1]);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Padding, Paragraph, Row, Table, TableState},
+};
+
+use crate::app::App;
+use crate::model::snapshot::Snapshot;
+use crate::ui::utils::{format_bytes, format_timestamp};
+
+#[derive(Debug, Default)]
+pub struct SnapshotScreen {
+    pub snapshots: Vec<Snapshot>,
+    pub state: TableState,
+    /// whether the user is being asked to confirm restoring the selected snapshot
+    pub confirm_restore: bool,
+}
+
+impl SnapshotScreen {
+    pub fn new(snapshots: Vec<Snapshot>) -> Self {
+        let mut state = TableState::default();
+        if !snapshots.is_empty() {
+            state.select(Some(0));
+        }
+        SnapshotScreen {
+            snapshots,
+            state,
+            confirm_restore: false,
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Snapshot> {
+        self.state.selected().and_then(|i| self.snapshots.get(i))
+    }
+
+    pub fn next(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state
+                .select(Some((i + 1).min(self.snapshots.len().saturating_sub(1))));
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(i.saturating_sub(1)));
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, app: &App, inner_area: Rect) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(inner_area);
+
+        let header = Paragraph::new(Line::from(format!(
+            "{} snapshots of {}",
+            self.snapshots.len(),
+            app.sql_path
+        )))
+        .block(Block::default().padding(Padding::horizontal(1)))
+        .fg(theme.header_text)
+        .bold();
+        frame.render_widget(header, chunks[0]);
+
+        let rows: Vec<Row> = self
+            .snapshots
+            .iter()
+            .map(|snapshot| {
+                Row::new(vec![
+                    format_timestamp(snapshot.taken_at),
+                    format_bytes(snapshot.size),
+                    snapshot.path.display().to_string(),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(21),
+                Constraint::Length(12),
+                Constraint::Min(0),
+            ],
+        )
+        .header(
+            Row::new(vec!["Taken (UTC)", "Size", "File"])
+                .style(
+                    Style::default()
+                        .fg(theme.header_text)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .bottom_margin(1),
+        )
+        .block(Block::default().padding(Padding::horizontal(1)))
+        .style(Style::default().fg(theme.body_text))
+        .row_highlight_style(Style::default().bg(theme.highlight).fg(Color::Black))
+        .highlight_symbol(">> ");
+        frame.render_stateful_widget(table, chunks[1], &mut self.state);
+
+        let footer = match self.selected() {
+            Some(snapshot) if self.confirm_restore => format!(
+                "Overwrite the database with the snapshot from {}? y/n",
+                format_timestamp(snapshot.taken_at)
+            ),
+            _ => "r restore  o open read-only  q close".to_string(),
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(footer))
+                .block(Block::default().padding(Padding::horizontal(1)))
+                .fg(theme.highlight),
+            chunks[2],
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Bar, BarChart, Block, Borders, Padding, Paragraph, Row, Table, TableState},
+};
+use std::cmp::Ordering;
+
+use crate::app::App;
+use crate::model::storage::{ObjectStorage, StorageReport};
+use crate::ui::table::SortDirection;
+use crate::ui::utils::format_bytes;
+
+/// how many objects the bar chart shows
+const CHART_OBJECTS: usize = 10;
+
+/// the columns the storage table can be sorted by, in the order `s` goes through them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageColumn {
+    Name,
+    Rows,
+    Pages,
+    Bytes,
+    Unused,
+    Fragmentation,
+}
+
+impl StorageColumn {
+    const ALL: [StorageColumn; 6] = [
+        StorageColumn::Name,
+        StorageColumn::Rows,
+        StorageColumn::Pages,
+        StorageColumn::Bytes,
+        StorageColumn::Unused,
+        StorageColumn::Fragmentation,
+    ];
+
+    fn next(&self) -> Self {
+        let i = Self::ALL
+            .iter()
+            .position(|column| column == self)
+            .unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            StorageColumn::Name => "Name",
+            StorageColumn::Rows => "Rows",
+            StorageColumn::Pages => "Pages",
+            StorageColumn::Bytes => "Size",
+            StorageColumn::Unused => "Unused",
+            StorageColumn::Fragmentation => "Fragmented",
+        }
+    }
+
+    fn compare(&self, a: &ObjectStorage, b: &ObjectStorage) -> Ordering {
+        match self {
+            StorageColumn::Name => a.name.cmp(&b.name),
+            StorageColumn::Rows => a.rows.cmp(&b.rows),
+            StorageColumn::Pages => a.pages.cmp(&b.pages),
+            StorageColumn::Bytes => a.bytes.cmp(&b.bytes),
+            StorageColumn::Unused => a.unused.cmp(&b.unused),
+            StorageColumn::Fragmentation => a
+                .fragmentation
+                .partial_cmp(&b.fragmentation)
+                .unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StorageScreen {
+    pub report: StorageReport,
+    pub sort: (StorageColumn, SortDirection),
+    pub state: TableState,
+}
+
+fn optional(value: Option<i64>, format: impl Fn(i64) -> String) -> String {
+    value.map(format).unwrap_or_else(|| "-".to_string())
+}
+
+impl StorageScreen {
+    pub fn new(report: StorageReport) -> Self {
+        // the biggest objects first, or the most rows when sizes are unknown
+        let column = if report.dbstat {
+            StorageColumn::Bytes
+        } else {
+            StorageColumn::Rows
+        };
+        let mut storage_screen = StorageScreen {
+            report,
+            sort: (column, SortDirection::Descending),
+            state: TableState::default().with_selected(Some(0)),
+        };
+        storage_screen.apply_sort();
+        storage_screen
+    }
+
+    /// swaps in a fresh report, keeping the sort
+    pub fn refresh(&mut self, report: StorageReport) {
+        self.report = report;
+        self.apply_sort();
+    }
+
+    fn apply_sort(&mut self) {
+        let (column, direction) = self.sort;
+        self.report.objects.sort_by(|a, b| match direction {
+            SortDirection::Ascending => column.compare(a, b),
+            SortDirection::Descending => column.compare(b, a),
+        });
+    }
+
+    /// sorts by the next column, largest first except for names
+    pub fn next_sort_column(&mut self) {
+        let column = self.sort.0.next();
+        let direction = if column == StorageColumn::Name {
+            SortDirection::Ascending
+        } else {
+            SortDirection::Descending
+        };
+        self.sort = (column, direction);
+        self.apply_sort();
+    }
+
+    pub fn reverse_sort(&mut self) {
+        self.sort.1 = match self.sort.1 {
+            SortDirection::Ascending => SortDirection::Descending,
+            SortDirection::Descending => SortDirection::Ascending,
+        };
+        self.apply_sort();
+    }
+
+    pub fn next(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(
+                (i + 1).min(self.report.objects.len().saturating_sub(1)),
+            ));
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(i.saturating_sub(1)));
+        }
+    }
+
+    /// the largest objects by size, or by rows without dbstat
+    fn chart_bars(&self, color: Color) -> Vec<Bar<'_>> {
+        let measure = |object: &ObjectStorage| {
+            if self.report.dbstat {
+                object.bytes
+            } else {
+                object.rows
+            }
+            .unwrap_or(0)
+            .max(0) as u64
+        };
+        let mut largest: Vec<&ObjectStorage> = self.report.objects.iter().collect();
+        largest.sort_by_key(|object| std::cmp::Reverse(measure(object)));
+        largest
+            .into_iter()
+            .take(CHART_OBJECTS)
+            .map(|object| {
+                let value = measure(object);
+                let text = if self.report.dbstat {
+                    format_bytes(value)
+                } else {
+                    value.to_string()
+                };
+                Bar::with_label(object.name.as_str(), value)
+                    .text_value(text)
+                    .style(Style::default().fg(color))
+                    .value_style(Style::default().fg(Color::Black).bg(color))
+            })
+            .collect()
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, app: &App, inner_area: Rect) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(inner_area);
+
+        let total: i64 = self
+            .report
+            .objects
+            .iter()
+            .filter_map(|object| object.bytes)
+            .sum();
+        let summary = if self.report.dbstat {
+            format!(
+                "Storage of {}: {} objects, {}",
+                app.sql_path,
+                self.report.objects.len(),
+                format_bytes(total as u64)
+            )
+        } else {
+            format!(
+                "Storage of {}: this SQLite was built without dbstat, so only rows are counted",
+                app.sql_path
+            )
+        };
+        let header_color = if self.report.dbstat {
+            theme.header_text
+        } else {
+            theme.warning
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(summary))
+                .block(Block::default().padding(Padding::horizontal(1)))
+                .fg(header_color)
+                .bold(),
+            chunks[0],
+        );
+
+        let body = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(chunks[1]);
+
+        let (sort_column, sort_direction) = self.sort;
+        let headings: Vec<String> = [
+            Some(StorageColumn::Name),
+            None,
+            Some(StorageColumn::Rows),
+            Some(StorageColumn::Pages),
+            Some(StorageColumn::Bytes),
+            Some(StorageColumn::Unused),
+            Some(StorageColumn::Fragmentation),
+        ]
+        .iter()
+        .map(|column| match column {
+            Some(column) if *column == sort_column => {
+                let arrow = match sort_direction {
+                    SortDirection::Ascending => "▲",
+                    SortDirection::Descending => "▼",
+                };
+                format!("{} {}", column.title(), arrow)
+            }
+            Some(column) => column.title().to_string(),
+            None => "Type".to_string(),
+        })
+        .collect();
+
+        let rows: Vec<Row> = self
+            .report
+            .objects
+            .iter()
+            .map(|object| {
+                Row::new(vec![
+                    object.name.clone(),
+                    object.kind.clone(),
+                    optional(object.rows, |rows| rows.to_string()),
+                    optional(object.pages, |pages| pages.to_string()),
+                    optional(object.bytes, |bytes| format_bytes(bytes as u64)),
+                    optional(object.unused, |unused| format_bytes(unused as u64)),
+                    object
+                        .fragmentation
+                        .map(|percent| format!("{:.0}%", percent))
+                        .unwrap_or_else(|| "-".to_string()),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(12),
+                Constraint::Length(6),
+                Constraint::Length(10),
+                Constraint::Length(9),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(13),
+            ],
+        )
+        .header(
+            Row::new(headings)
+                .style(
+                    Style::default()
+                        .fg(theme.header_text)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .bottom_margin(1),
+        )
+        .block(Block::default().padding(Padding::horizontal(1)))
+        .style(Style::default().fg(theme.body_text))
+        .row_highlight_style(Style::default().bg(theme.highlight).fg(Color::Black));
+        frame.render_stateful_widget(table, body[0], &mut self.state);
+
+        let chart_title = if self.report.dbstat {
+            " Largest objects "
+        } else {
+            " Most rows "
+        };
+        let chart = BarChart::horizontal(self.chart_bars(theme.highlight))
+            .block(
+                Block::default()
+                    .borders(Borders::LEFT)
+                    .border_style(Style::default().fg(theme.inner_border))
+                    .title(chart_title)
+                    .padding(Padding::horizontal(1)),
+            )
+            .bar_gap(0)
+            .label_style(Style::default().fg(theme.body_text));
+        frame.render_widget(chart, body[1]);
+
+        frame.render_widget(
+            Paragraph::new(Line::from(
+                "s sort by the next column  o reverse the sort  r refresh  q close",
+            ))
+            .block(Block::default().padding(Padding::horizontal(1)))
+            .fg(theme.highlight),
+            chunks[2],
+        );
+    }
+}
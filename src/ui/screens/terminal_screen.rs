@@ -1,18 +1,28 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Style, Stylize},
+    style::{Color, Style, Stylize},
     symbols::border,
-    text::Line,
-    widgets::{Block, Padding, Paragraph},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListItem, ListState, Padding, Paragraph},
 };
 use std::collections::VecDeque;
 
 use crate::app::App;
+use crate::model::completion::{Suggestion, complete};
+use crate::model::schema::SchemaCache;
 
 const MAX_HISTORY_LENGTH: usize = 100;
 const MAX_LOG_LINES: usize = 1000;
 const MAX_INPUT_LENGTH: usize = 2048;
+const MAX_COMPLETION_ROWS: u16 = 8;
+
+#[derive(Debug, Default)]
+pub struct CompletionPopup {
+    pub word_start: usize,
+    pub suggestions: Vec<Suggestion>,
+    pub state: ListState,
+}
 
 #[derive(Debug, Default)]
 pub struct TerminalScreen {
@@ -23,6 +33,7 @@ pub struct TerminalScreen {
     pub displayed_lines: VecDeque<String>,
     // Cache to reduce allocations during history navigation
     input_backup: Option<String>,
+    pub completion: Option<CompletionPopup>,
 }
 
 impl TerminalScreen {
@@ -35,6 +46,7 @@ impl TerminalScreen {
             cursor_index: 0,
             displayed_lines: VecDeque::with_capacity(MAX_LOG_LINES),
             input_backup: None,
+            completion: None,
         }
     }
 
@@ -88,6 +100,135 @@ impl TerminalScreen {
             input_area.x + 1 + (cursor_offset_in_para - scroll_x),
             input_area.y + 1,
         ));
+
+        if self.completion.is_some() {
+            let word_offset = self
+                .completion
+                .as_ref()
+                .map_or(0, |c| (2 + c.word_start) as u16)
+                .saturating_sub(scroll_x);
+            self.render_completion(frame, app, input_area, word_offset);
+        }
+    }
+
+    fn render_completion(&self, frame: &mut Frame, app: &App, input_area: Rect, word_offset: u16) {
+        let Some(completion) = &self.completion else {
+            return;
+        };
+
+        let text_width = completion
+            .suggestions
+            .iter()
+            .map(|s| s.text.chars().count() + s.kind.label().len() + 1)
+            .max()
+            .unwrap_or(0) as u16;
+        let width = (text_width + 4).min(input_area.width);
+        let height = (completion.suggestions.len() as u16).min(MAX_COMPLETION_ROWS) + 2;
+        let x = (input_area.x + 1 + word_offset).min(input_area.right().saturating_sub(width));
+        let popup_area = Rect {
+            x,
+            y: input_area.y.saturating_sub(height),
+            width,
+            height: height.min(input_area.y),
+        };
+
+        let items: Vec<ListItem> = completion
+            .suggestions
+            .iter()
+            .map(|suggestion| {
+                let padding = (text_width as usize).saturating_sub(
+                    suggestion.text.chars().count() + suggestion.kind.label().len(),
+                );
+                ListItem::new(Line::from(vec![
+                    Span::raw(suggestion.text.clone()),
+                    Span::raw(" ".repeat(padding)),
+                    Span::raw(suggestion.kind.label()).fg(app.theme.inner_border),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::bordered()
+                    .border_style(Style::default().fg(app.theme.inner_border))
+                    .border_set(border::ROUNDED),
+            )
+            .style(
+                Style::default()
+                    .fg(app.theme.body_text)
+                    .bg(app.theme.background),
+            )
+            .highlight_style(Style::default().bg(app.theme.highlight).fg(Color::Black));
+
+        let mut state = completion.state;
+        frame.render_widget(Clear, popup_area);
+        frame.render_stateful_widget(list, popup_area, &mut state);
+    }
+
+    // completion operations
+    pub fn open_completion(&mut self, schema: &SchemaCache) {
+        let refining = self.completion.is_some();
+        let completion = complete(&self.input, self.cursor_index, schema);
+        if completion.suggestions.is_empty() {
+            self.completion = None;
+            return;
+        }
+
+        let single_match = completion.suggestions.len() == 1;
+        self.completion = Some(CompletionPopup {
+            word_start: completion.word_start,
+            suggestions: completion.suggestions,
+            state: ListState::default().with_selected(Some(0)),
+        });
+
+        // a single candidate is inserted straight away unless the popup is being refined
+        if single_match && !refining {
+            self.accept_completion();
+        }
+    }
+
+    pub fn close_completion(&mut self) {
+        self.completion = None;
+    }
+
+    pub fn next_completion(&mut self) {
+        if let Some(completion) = &mut self.completion {
+            let len = completion.suggestions.len();
+            let i = completion.state.selected().map_or(0, |i| (i + 1) % len);
+            completion.state.select(Some(i));
+        }
+    }
+
+    pub fn previous_completion(&mut self) {
+        if let Some(completion) = &mut self.completion {
+            let len = completion.suggestions.len();
+            let i = completion
+                .state
+                .selected()
+                .map_or(0, |i| (i + len - 1) % len);
+            completion.state.select(Some(i));
+        }
+    }
+
+    pub fn accept_completion(&mut self) {
+        let Some(completion) = self.completion.take() else {
+            return;
+        };
+        let Some(suggestion) = completion
+            .state
+            .selected()
+            .and_then(|i| completion.suggestions.get(i))
+        else {
+            return;
+        };
+
+        let mut chars: Vec<char> = self.input.chars().collect();
+        let end = self.cursor_index.min(chars.len());
+        let insertion: Vec<char> = suggestion.insertion().chars().collect();
+        self.cursor_index = completion.word_start + insertion.len();
+        chars.splice(completion.word_start..end, insertion);
+        self.input = chars.into_iter().take(MAX_INPUT_LENGTH).collect();
+        self.cursor_index = self.cursor_index.min(self.input.chars().count());
     }
 
     pub fn add_log_line(&mut self, line: String) {
@@ -98,6 +239,20 @@ impl TerminalScreen {
         self.displayed_lines.push_back(line);
     }
 
+    /// the log lines printed by the command before the most recent one
+    pub fn last_output(&self) -> Option<String> {
+        let mut lines = self.displayed_lines.iter().rev().skip(1);
+        let output: Vec<&str> = lines
+            .by_ref()
+            .take_while(|line| !line.starts_with("> "))
+            .map(|line| line.as_str())
+            .collect();
+        if output.is_empty() {
+            return None;
+        }
+        Some(output.into_iter().rev().collect::<Vec<&str>>().join("\n"))
+    }
+
     // input operations
     pub fn move_cursor_left(&mut self) {
         if self.cursor_index == 0 {
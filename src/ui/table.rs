@@ -1,31 +1,75 @@
+use crate::model::filter::RowFilter;
+use crate::model::value::{compare_values, display_value, json_string, json_value};
 use ratatui::widgets::{ScrollbarState, TableState};
+use rusqlite::types::Value;
+use std::collections::HashMap;
+use unicode_width::UnicodeWidthStr;
 
 const ITEM_HEIGHT: usize = 4;
+// ">> " highlight symbol plus the horizontal padding of the table block
+const TABLE_CHROME_WIDTH: u16 = 5;
+const COLUMN_SPACING: u16 = 1;
+const RESIZE_STEP: u16 = 2;
+const MIN_COLUMN_WIDTH: u16 = 3;
+
+fn display_width(text: &str) -> u16 {
+    text.width().min(u16::MAX as usize) as u16
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug)]
+pub struct TableRow {
+    /// position of the row in the original query result
+    pub index: usize,
+    pub cells: Vec<String>,
+    pub values: Vec<Value>,
+}
+
+impl TableRow {
+    pub fn get(&self, i: usize) -> Option<&String> {
+        self.cells.get(i)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, String> {
+        self.cells.iter()
+    }
+}
 
 #[derive(Debug)]
 pub struct TableData {
     pub columns: Vec<String>,
-    pub max_lengths: Vec<u8>,
-    pub rows: Vec<Vec<String>>,
+    pub max_lengths: Vec<u16>,
+    pub rows: Vec<TableRow>,
+    /// rows removed by the active filter
+    pub hidden: Vec<TableRow>,
 }
 
 impl TableData {
-    pub fn new(columns: Vec<String>, rows: Vec<Vec<String>>) -> Self {
-        // calculate the max_lengths for each columns
-        let mut max_lengths: Vec<u8> = columns.iter().map(|s| s.len() as u8).collect();
+    pub fn new(columns: Vec<String>, values: Vec<Vec<Value>>) -> Self {
+        let rows: Vec<TableRow> = values
+            .into_iter()
+            .enumerate()
+            .map(|(index, values)| TableRow {
+                index,
+                cells: values.iter().map(display_value).collect(),
+                values,
+            })
+            .collect();
 
-        if !rows.is_empty() {
-            for i in 0..columns.len() {
-                let max_in_col = rows
-                    .iter()
-                    .map(|row| row.get(i).map(|cell| cell.len()).unwrap_or(0))
-                    .max()
-                    .unwrap_or(0) as u8;
+        // calculate the display width of each column
+        let mut max_lengths: Vec<u16> = columns.iter().map(|s| display_width(s)).collect();
 
-                if i < max_lengths.len() {
-                    max_lengths[i] = max_lengths[i].max(max_in_col);
-                } else {
-                    max_lengths.push(max_in_col);
+        for row in &rows {
+            for (i, cell) in row.iter().enumerate() {
+                let width = display_width(cell);
+                match max_lengths.get_mut(i) {
+                    Some(max_length) => *max_length = (*max_length).max(width),
+                    None => max_lengths.push(width),
                 }
             }
         }
@@ -34,8 +78,69 @@ impl TableData {
             columns,
             max_lengths,
             rows,
+            hidden: Vec::new(),
+        }
+    }
+
+    pub fn total_rows(&self) -> usize {
+        self.rows.len() + self.hidden.len()
+    }
+
+    pub fn apply_filter(&mut self, filter: &RowFilter) {
+        let (rows, hidden): (Vec<TableRow>, Vec<TableRow>) = std::mem::take(&mut self.rows)
+            .into_iter()
+            .partition(|row| filter.matches(&row.cells, &row.values));
+        self.rows = rows;
+        self.hidden.extend(hidden);
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.rows.append(&mut self.hidden);
+        self.restore_order();
+    }
+
+    pub fn sort_by_column(&mut self, column: usize, direction: SortDirection) {
+        self.rows.sort_by(|a, b| {
+            let ordering = match (a.values.get(column), b.values.get(column)) {
+                (Some(x), Some(y)) => compare_values(x, y),
+                _ => std::cmp::Ordering::Equal,
+            };
+            match direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        });
+    }
+
+    pub fn restore_order(&mut self) {
+        self.rows.sort_by_key(|row| row.index);
+    }
+}
+
+/// the user's arrangement of result columns, remembered per query text
+#[derive(Debug, Clone, Default)]
+pub struct ColumnLayout {
+    /// data column indices in display order; columns left out are hidden
+    pub order: Vec<usize>,
+    /// number of leading columns kept on screen while scrolling horizontally
+    pub pinned: usize,
+    /// widths set by the user, keyed by data column
+    pub widths: HashMap<usize, u16>,
+}
+
+impl ColumnLayout {
+    pub fn new(column_count: usize) -> Self {
+        ColumnLayout {
+            order: (0..column_count).collect(),
+            pinned: 0,
+            widths: HashMap::new(),
         }
     }
+
+    fn fits(&self, column_count: usize) -> bool {
+        self.order.iter().all(|column| *column < column_count)
+            && self.widths.keys().all(|column| *column < column_count)
+    }
 }
 
 #[derive(Debug)]
@@ -43,55 +148,422 @@ pub struct TableView {
     pub data: TableData,
     pub state: TableState,
     pub scroll_state: ScrollbarState,
+    pub sort: Option<(usize, SortDirection)>,
+    pub search: Option<SearchState>,
+    pub filter: Option<RowFilter>,
+    pub layout: ColumnLayout,
+    /// first unpinned column drawn when the table is wider than the screen
+    pub column_offset: usize,
+    /// number of rows that fit on screen, updated on every render
+    pub page_height: usize,
+}
+
+#[derive(Debug)]
+pub struct SearchState {
+    pub pattern: String,
+    /// (row, display column) positions of every matching cell
+    pub matches: Vec<(usize, usize)>,
+    pub current: Option<usize>,
+}
+
+impl SearchState {
+    pub fn cell_matches(&self, cell: &str) -> bool {
+        cell.to_lowercase().contains(&self.pattern)
+    }
 }
 
 impl TableView {
-    pub fn new(columns: Vec<String>, rows: Vec<Vec<String>>) -> Self {
+    pub fn new(columns: Vec<String>, rows: Vec<Vec<Value>>) -> Self {
+        let layout = ColumnLayout::new(columns.len());
         let state = TableState::default().with_offset(0);
         let scroll_state: ScrollbarState = ScrollbarState::new((rows.len() - 1) * ITEM_HEIGHT);
         TableView {
             data: TableData::new(columns, rows),
             state,
             scroll_state,
+            sort: None,
+            search: None,
+            filter: None,
+            layout,
+            column_offset: 0,
+            page_height: 0,
         }
     }
 
+    /// data column under the cursor, as opposed to its position on screen
+    pub fn selected_data_column(&self) -> Option<usize> {
+        self.layout
+            .order
+            .get(self.state.selected_column().unwrap_or(0))
+            .copied()
+    }
+
     // Add navigation operations
-    pub fn next_row(&mut self) {
+    /// moves the selection by `delta` rows, stopping at the first and last row
+    pub fn move_rows(&mut self, delta: isize) {
+        if self.data.rows.is_empty() {
+            return;
+        }
+        let last = self.data.rows.len() - 1;
         let i = match self.state.selected() {
-            Some(i) => {
-                if i >= self.data.rows.len() - 1 {
-                    0
-                } else {
-                    i + 1
-                }
-            }
+            Some(i) => i.saturating_add_signed(delta).min(last),
             None => 0,
         };
         self.state.select(Some(i));
         self.scroll_state = self.scroll_state.position(i * ITEM_HEIGHT);
     }
 
-    pub fn previous_row(&mut self) {
-        let i = match self.state.selected() {
-            Some(i) => {
-                if i == 0 {
-                    self.data.rows.len() - 1
+    pub fn page_down(&mut self) {
+        self.move_rows(self.page_height.max(1) as isize);
+    }
+
+    pub fn page_up(&mut self) {
+        self.move_rows(-(self.page_height.max(1) as isize));
+    }
+
+    pub fn half_page_down(&mut self) {
+        self.move_rows((self.page_height / 2).max(1) as isize);
+    }
+
+    pub fn half_page_up(&mut self) {
+        self.move_rows(-((self.page_height / 2).max(1) as isize));
+    }
+
+    /// selects a row by its 1-based number, as shown in the status line
+    pub fn go_to_row(&mut self, row_number: usize) {
+        if self.data.rows.is_empty() {
+            return;
+        }
+        let i = row_number.saturating_sub(1).min(self.data.rows.len() - 1);
+        self.state.select(Some(i));
+        self.scroll_state = self.scroll_state.position(i * ITEM_HEIGHT);
+    }
+
+    pub fn first_row(&mut self) {
+        self.go_to_row(1);
+    }
+
+    pub fn last_row(&mut self) {
+        self.go_to_row(self.data.rows.len());
+    }
+
+    pub fn move_columns(&mut self, delta: isize) {
+        let last = self.layout.order.len().saturating_sub(1);
+        let column = self
+            .state
+            .selected_column()
+            .map_or(0, |c| c.saturating_add_signed(delta).min(last));
+        self.state.select_column(Some(column));
+    }
+
+    pub fn first_column(&mut self) {
+        self.state.select_column(Some(0));
+    }
+
+    pub fn last_column(&mut self) {
+        let last = self.layout.order.len().saturating_sub(1);
+        self.state.select_column(Some(last));
+    }
+
+    /// cycles the selected column through ascending, descending and the original order
+    pub fn cycle_sort(&mut self) {
+        let Some(column) = self.selected_data_column() else {
+            return;
+        };
+        self.sort = match self.sort {
+            Some((sorted, SortDirection::Ascending)) if sorted == column => {
+                Some((column, SortDirection::Descending))
+            }
+            Some((sorted, SortDirection::Descending)) if sorted == column => None,
+            _ => Some((column, SortDirection::Ascending)),
+        };
+
+        self.apply_sort();
+        self.refresh_search();
+    }
+
+    fn apply_sort(&mut self) {
+        match self.sort {
+            Some((column, direction)) => self.data.sort_by_column(column, direction),
+            None => self.data.restore_order(),
+        }
+    }
+
+    /// width of each data column, capped or set by the user, leaving room for the sort arrow
+    pub fn column_widths(&self, max_column_width: u16) -> Vec<u16> {
+        self.data
+            .max_lengths
+            .iter()
+            .enumerate()
+            .map(|(i, length)| match self.layout.widths.get(&i) {
+                Some(width) => *width,
+                None => (*length).min(max_column_width) + 2,
+            })
+            .collect()
+    }
+
+    /// scrolls horizontally so the selected column fits and returns the display positions to draw
+    pub fn visible_columns(&mut self, area_width: u16, widths: &[u16]) -> Vec<usize> {
+        let available = area_width.saturating_sub(TABLE_CHROME_WIDTH);
+        let column_count = self.layout.order.len();
+        let pinned = self.layout.pinned.min(column_count);
+        let selected = self.state.selected_column().unwrap_or(0);
+        let width_at = |position: usize| widths[self.layout.order[position]];
+
+        self.column_offset = self.column_offset.max(pinned);
+        if selected >= pinned {
+            self.column_offset = self.column_offset.min(selected);
+        }
+
+        loop {
+            let mut used = 0;
+            let mut columns: Vec<usize> = Vec::new();
+            for position in (0..pinned).chain(self.column_offset..column_count) {
+                let needed = if columns.is_empty() {
+                    width_at(position)
                 } else {
-                    i - 1
+                    width_at(position) + COLUMN_SPACING
+                };
+                // the first column is always drawn, even if it has to be clipped
+                if !columns.is_empty() && used + needed > available {
+                    break;
                 }
+                used += needed;
+                columns.push(position);
             }
-            None => 0,
+
+            if selected < pinned || columns.contains(&selected) || self.column_offset >= selected {
+                return columns;
+            }
+            self.column_offset += 1;
+        }
+    }
+
+    // column layout operations
+    pub fn apply_layout(&mut self, layout: ColumnLayout) {
+        if layout.fits(self.data.columns.len()) {
+            self.layout = layout;
+            self.refresh_search();
+        }
+    }
+
+    pub fn hide_column(&mut self) {
+        // keep at least one column on screen
+        if self.layout.order.len() <= 1 {
+            return;
+        }
+        let position = self.state.selected_column().unwrap_or(0);
+        if position < self.layout.order.len() {
+            self.layout.order.remove(position);
+            if position < self.layout.pinned {
+                self.layout.pinned -= 1;
+            }
+            let last = self.layout.order.len() - 1;
+            self.state.select_column(Some(position.min(last)));
+            self.refresh_search();
+        }
+    }
+
+    pub fn show_all_columns(&mut self) {
+        for column in 0..self.data.columns.len() {
+            if !self.layout.order.contains(&column) {
+                self.layout.order.push(column);
+            }
+        }
+        self.refresh_search();
+    }
+
+    pub fn move_column_left(&mut self) {
+        let position = self.state.selected_column().unwrap_or(0);
+        if position > 0 && position < self.layout.order.len() {
+            self.layout.order.swap(position, position - 1);
+            self.state.select_column(Some(position - 1));
+            self.refresh_search();
+        }
+    }
+
+    pub fn move_column_right(&mut self) {
+        let position = self.state.selected_column().unwrap_or(0);
+        if position + 1 < self.layout.order.len() {
+            self.layout.order.swap(position, position + 1);
+            self.state.select_column(Some(position + 1));
+            self.refresh_search();
+        }
+    }
+
+    /// pins every column up to and including the selected one, or unpins if already pinned there
+    pub fn toggle_pin(&mut self) {
+        let pinned = self.state.selected_column().unwrap_or(0) + 1;
+        self.layout.pinned = if self.layout.pinned == pinned {
+            0
+        } else {
+            pinned
         };
-        self.state.select(Some(i));
-        self.scroll_state = self.scroll_state.position(i * ITEM_HEIGHT);
     }
 
-    pub fn next_column(&mut self) {
-        self.state.select_next_column();
+    pub fn resize_column(&mut self, grow: bool, max_column_width: u16) {
+        let Some(column) = self.selected_data_column() else {
+            return;
+        };
+        let current = self.column_widths(max_column_width)[column];
+        let width = if grow {
+            current.saturating_add(RESIZE_STEP)
+        } else {
+            current.saturating_sub(RESIZE_STEP).max(MIN_COLUMN_WIDTH)
+        };
+        self.layout.widths.insert(column, width);
+    }
+
+    // clipboard text for the selection, following the on-screen column layout
+    pub fn selected_cell_text(&self) -> Option<String> {
+        let row = self.data.rows.get(self.state.selected()?)?;
+        row.get(self.selected_data_column()?).cloned()
+    }
+
+    pub fn selected_row_tsv(&self) -> Option<String> {
+        let row = self.data.rows.get(self.state.selected()?)?;
+        let cells: Vec<&str> = self
+            .layout
+            .order
+            .iter()
+            .map(|column| row.get(*column).map_or("", |cell| cell.as_str()))
+            .collect();
+        Some(cells.join("\t"))
+    }
+
+    pub fn selected_row_json(&self) -> Option<String> {
+        let row = self.data.rows.get(self.state.selected()?)?;
+        let fields: Vec<String> = self
+            .layout
+            .order
+            .iter()
+            .filter_map(|column| {
+                Some(format!(
+                    "{}: {}",
+                    json_string(self.data.columns.get(*column)?),
+                    json_value(row.values.get(*column)?)
+                ))
+            })
+            .collect();
+        Some(format!("{{{}}}", fields.join(", ")))
+    }
+
+    pub fn selected_column_text(&self) -> Option<String> {
+        let column = self.selected_data_column()?;
+        let cells: Vec<&str> = self
+            .data
+            .rows
+            .iter()
+            .map(|row| row.get(column).map_or("", |cell| cell.as_str()))
+            .collect();
+        Some(cells.join("\n"))
+    }
+
+    pub fn select_cell(&mut self, row: usize, column: usize) {
+        self.state.select(Some(row));
+        self.state.select_column(Some(column));
+        self.scroll_state = self.scroll_state.position(row * ITEM_HEIGHT);
     }
 
-    pub fn previous_column(&mut self) {
-        self.state.select_previous_column();
+    // search operations
+    pub fn search(&mut self, pattern: &str) {
+        if pattern.is_empty() {
+            self.search = None;
+            return;
+        }
+        self.search = Some(SearchState {
+            pattern: pattern.to_lowercase(),
+            matches: Vec::new(),
+            current: None,
+        });
+        self.refresh_search();
+        self.next_match();
+    }
+
+    /// recomputes match positions after the rows or columns were rearranged
+    fn refresh_search(&mut self) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+        let order = &self.layout.order;
+        search.matches = self
+            .data
+            .rows
+            .iter()
+            .enumerate()
+            .flat_map(|(row_index, row)| {
+                order
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, column)| {
+                        row.get(**column)
+                            .is_some_and(|cell| cell.to_lowercase().contains(&search.pattern))
+                    })
+                    .map(move |(position, _)| (row_index, position))
+            })
+            .collect();
+        search.current = None;
+    }
+
+    pub fn next_match(&mut self) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+        if search.matches.is_empty() {
+            return;
+        }
+        // start from the selected cell when no match has been visited yet
+        let next = match search.current {
+            Some(current) => (current + 1) % search.matches.len(),
+            None => {
+                let position = (
+                    self.state.selected().unwrap_or(0),
+                    self.state.selected_column().unwrap_or(0),
+                );
+                search
+                    .matches
+                    .iter()
+                    .position(|found| *found >= position)
+                    .unwrap_or(0)
+            }
+        };
+        search.current = Some(next);
+        let (row, column) = search.matches[next];
+        self.select_cell(row, column);
+    }
+
+    pub fn previous_match(&mut self) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+        if search.matches.is_empty() {
+            return;
+        }
+        let len = search.matches.len();
+        let previous = search
+            .current
+            .map_or(len - 1, |current| (current + len - 1) % len);
+        search.current = Some(previous);
+        let (row, column) = search.matches[previous];
+        self.select_cell(row, column);
+    }
+
+    // filter operations
+    pub fn set_filter(&mut self, filter: Option<RowFilter>) {
+        self.data.clear_filter();
+        if let Some(filter) = &filter {
+            self.data.apply_filter(filter);
+        }
+        self.filter = filter;
+        self.apply_sort();
+        self.refresh_search();
+
+        self.scroll_state =
+            ScrollbarState::new(self.data.rows.len().saturating_sub(1) * ITEM_HEIGHT);
+        if self.data.rows.is_empty() {
+            self.state.select(None);
+        } else {
+            self.select_cell(0, self.state.selected_column().unwrap_or(0));
+        }
     }
 }
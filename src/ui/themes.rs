@@ -8,6 +8,7 @@ pub struct ColorPalette {
     pub body_text: Color,
     pub highlight: Color,
     pub background: Color,
+    pub warning: Color,
 }
 
 #[allow(dead_code)]
@@ -21,6 +22,7 @@ impl ColorPalette {
             body_text: Color::from_u32(0xc0caf5),    // foreground
             highlight: Color::from_u32(0x73daca),    // cyan
             background: Color::from_u32(0x1a1b26),   // dark
+            warning: Color::from_u32(0xf7768e),      // red
         }
     }
 
@@ -33,6 +35,7 @@ impl ColorPalette {
             body_text: Color::from_u32(0xcdd6f4),    // text
             highlight: Color::from_u32(0x94e2d5),    // teal
             background: Color::from_u32(0x1e1e2e),   // base
+            warning: Color::from_u32(0xfab387),      // peach
         }
     }
 
@@ -45,6 +48,7 @@ impl ColorPalette {
             body_text: Color::from_u32(0xf8f8f2),    // foreground
             highlight: Color::from_u32(0x8be9fd),    // cyan
             background: Color::from_u32(0x282a36),   // background
+            warning: Color::from_u32(0xff5555),      // red
         }
     }
 
@@ -57,6 +61,7 @@ impl ColorPalette {
             body_text: Color::from_u32(0xebdbb2),    // foreground
             highlight: Color::from_u32(0x689d6a),    // aqua
             background: Color::from_u32(0x282828),   // dark0
+            warning: Color::from_u32(0xcc241d),      // red
         }
     }
 
@@ -69,6 +74,7 @@ impl ColorPalette {
             body_text: Color::from_u32(0x839496),    // base0
             highlight: Color::from_u32(0x2aa198),    // cyan
             background: Color::from_u32(0x002b36),   // base03
+            warning: Color::from_u32(0xdc322f),      // red
         }
     }
 
@@ -81,6 +87,7 @@ impl ColorPalette {
             body_text: Color::from_u32(0xECEFF4),    // foreground
             highlight: Color::from_u32(0x8FBCBB),    // cyan
             background: Color::from_u32(0x2E3440),   // polar night
+            warning: Color::from_u32(0xBF616A),      // red
         }
     }
 
@@ -93,6 +100,7 @@ impl ColorPalette {
             body_text: Color::from_u32(0xF8F8F2),    // foreground
             highlight: Color::from_u32(0x66D9EF),    // cyan
             background: Color::from_u32(0x272822),   // background
+            warning: Color::from_u32(0xF92672),      // pink
         }
     }
 }